@@ -0,0 +1,12 @@
+use cosmwasm_schema::write_api;
+use empty_contract::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
+
+fn main() { // writes the JSON schema of every message into ./schema, run with `cargo schema`
+    write_api! {
+        instantiate: InstantiateMsg,
+        execute: ExecuteMsg,
+        query: QueryMsg,
+        migrate: MigrateMsg,
+        sudo: SudoMsg,
+    }
+}
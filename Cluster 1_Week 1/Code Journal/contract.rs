@@ -1,7 +1,33 @@
 use crate::error::ContractError;
-use crate::msg::{AdminsListResp, ExecuteMsg, GreetResp, InstantiateMsg, QueryMsg};
-use crate::state::ADMINS;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use crate::msg::{
+    AddedByResp, AdminRecord, AdminsAddedBetweenResp, AdminsByMinWeightResp, AdminsByRankResp,
+    AdminsByRoleResp, AdminsListResp, AdminsPreviewResp, AllowlistQueryMsg, AnnotationsResp,
+    AvailabilityResp, BlockInfoResp, BothAdminsResp, CanExecuteResp, CanJoinResp,
+    ConfigMatchesResp, CountResp, CreatedAtResp, Cw4ExecuteMsg, Cw4Member, DelegationsResp,
+    DiffFromResp, EffectiveAuthorityResp, ExecuteMsg, ExpiringSoonResp, FreezeStatusResp,
+    GreetResp, InactiveSinceResp, IndexOfResp, InstantiateMsg, InstantiateResult, IntegrityResp,
+    IntersectionResp, InviteEntry, InvitesResp, IsAllowedResp, IsBrickedResp, LogExecuteMsg,
+    MigrateMsg, NextExpiryResp, NotifierExecuteMsg, PayoutPlanResp, PendingOwnerResp,
+    PendingRemovalsResp, PendingSelfAcceptResp, PolicyResp, PrunableProposalsResp, QueryMsg,
+    ReconcileResp, RemovableByResp, RemoveResult, SelfAddressResp, StateExport,
+    StateFingerprintResp, StatusCountsResp, SudoMsg, TotalDonatedResp,
+};
+use crate::state::{
+    add_admin, admins_ordered, admins_ordered_page, is_active, is_expired, record_donation,
+    remove_admin_fully, role_capabilities, AdminInfo, Capabilities, Config, Invite, PendingOwner,
+    RateGuard, Role, ADMINS, ADMINS_BY_JOINED, ADMIN_COUNT, ANNOTATIONS, CONFIG, CREATED,
+    DELEGATIONS, FREEZE_AT, FROZEN, GREETING, INVITES, LABELS, LAST_ACTION, LEGACY_ADMINS,
+    MIN_DONATIONS, OWNER, PENDING_OWNER, PENDING_REMOVALS, PROPOSED_ADMINS, PROPOSED_AT, READ_ONLY,
+    RECENT_CHANGES, REMOVAL_PROPOSED_AT, ROLE_CAPABILITIES, ROSTER_CHANGES, STATE_VERSION,
+    TOTAL_DONATED,
+};
+use cosmwasm_std::{
+    to_binary, to_vec, Addr, Api, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, Event,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, Timestamp, Uint128, WasmMsg,
+};
+use cw_storage_plus::{Bound, Map};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 
 
 
@@ -23,18 +49,697 @@ use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response,
 // }
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
-    let admins: StdResult<Vec<_>> = msg
-        .admins
+) -> Result<Response, ContractError> {
+    if let Some(expected) = &msg.expected_sender {
+        let expected = deps.api.addr_validate(expected)?;
+        if info.sender != expected {
+            return Err(ContractError::UnexpectedSender {
+                expected,
+                sender: info.sender,
+            });
+        }
+    }
+    OWNER.save(deps.storage, &info.sender)?;
+    let config = instantiate_config(deps.as_ref(), &msg)?;
+    CONFIG.save(deps.storage, &config)?;
+    for (role, capabilities) in msg.role_capabilities {
+        ROLE_CAPABILITIES.save(deps.storage, role.key(), &capabilities)?;
+    }
+    for (denom, min) in msg.min_donations {
+        validate_denom(&denom)?;
+        MIN_DONATIONS.save(deps.storage, &denom, &min)?;
+    }
+    CREATED.save(deps.storage, &(env.block.height, env.block.time))?;
+    STATE_VERSION.save(deps.storage, &CURRENT_STATE_VERSION)?;
+    GREETING.save(deps.storage, &"Hello World".to_owned())?;
+    ADMIN_COUNT.save(deps.storage, &0)?;
+
+    let admin_info = AdminInfo::new(&env.block, info.sender.clone());
+    let mut admins = msg.admins;
+    if msg.owner_must_be_admin {
+        admins.push(info.sender.to_string());
+    }
+    let mut added = vec![];
+    for addr in admins {
+        let addr = validate_admin_addr(deps.as_ref(), &addr)?;
+        if add_admin(deps.storage, deps.api, &addr, &admin_info)? {
+            added.push((addr, admin_info.weight));
+        }
+    }
+    for (addr, label) in msg.labels {
+        let addr = validate_admin_addr(deps.as_ref(), &addr)?;
+        if !ADMINS.has(deps.storage, &addr) {
+            return Err(ContractError::NotAdmin { addr });
+        }
+        validate_label(deps.as_ref(), &label)?;
+        LABELS.save(deps.storage, &addr, &label)?;
+    }
+    // starting without admins is the same as having removed the last one
+    added.extend(exec::activate_fallback(deps.storage, deps.api, &env)?);
+
+    ensure_weight_cap(deps.as_ref())?;
+    ensure_admin_cap(deps.as_ref())?;
+
+    let result = InstantiateResult {
+        owner: info.sender,
+        admin_count: ADMIN_COUNT.load(deps.storage)?,
+    };
+    // funds sent along are an initial donation to the initial admins, held to the same rules as DonateTo
+    if !info.funds.is_empty() {
+        validate_donation(deps.as_ref(), &info.funds)?;
+    }
+    let initial_admins: Vec<_> = added.iter().map(|(addr, _)| addr.clone()).collect();
+    let donation = split_funds(&info.funds, &initial_admins);
+    record_donation(deps.storage, &info.funds)?;
+    let welcome = welcome_msgs(deps.as_ref(), &initial_admins)?;
+    let cw4 = cw4_update_msg(deps.as_ref(), vec![], added)?;
+    Ok(Response::new()
+        .add_messages(cw4)
+        .add_messages(welcome)
+        .add_messages(donation)
+        .set_data(to_binary(&result)?))
+}
+
+// the Config instantiate stores for `msg`, also what migrate sets up for the original contract
+fn instantiate_config(deps: Deps, msg: &InstantiateMsg) -> Result<Config, ContractError> {
+    let log_contract = msg
+        .log_contract
+        .as_ref()
+        .map(|addr| deps.api.addr_validate(addr))
+        .transpose()?;
+    let cw4_group = msg
+        .cw4_group
+        .as_ref()
+        .map(|addr| deps.api.addr_validate(addr))
+        .transpose()?;
+    let migration_source = msg
+        .migration_source
+        .as_ref()
+        .map(|addr| deps.api.addr_validate(addr))
+        .transpose()?;
+    let allowlist_contract = msg
+        .allowlist_contract
+        .as_ref()
+        .map(|addr| deps.api.addr_validate(addr))
+        .transpose()?;
+    let fallback_admin = msg
+        .fallback_admin
+        .as_ref()
+        .map(|addr| deps.api.addr_validate(addr))
+        .transpose()?;
+    let notify_on_join = msg
+        .notify_on_join
+        .as_ref()
+        .map(|addr| deps.api.addr_validate(addr))
+        .transpose()?;
+    for denom in &msg.blocked_denoms {
+        validate_denom(denom)?;
+    }
+    Ok(Config {
+        log_contract,
+        greeting_locked: msg.greeting_locked,
+        strict_limits: msg.strict_limits,
+        cw4_group,
+        min_delegation: msg.min_delegation,
+        default_role: msg.default_role.unwrap_or(Role::Super),
+        transfer_delay: msg.transfer_delay,
+        min_tenure: msg.min_tenure,
+        maintenance_until: None,
+        migration_source,
+        per_admin_cooldown: msg.per_admin_cooldown,
+        allowlist_contract,
+        require_acceptance: msg.require_acceptance,
+        fallback_admin,
+        max_label_len: msg.max_label_len.unwrap_or(DEFAULT_MAX_LABEL_LEN),
+        notify_on_join,
+        removal_threshold: msg.removal_threshold,
+        owner_must_be_admin: msg.owner_must_be_admin,
+        blocked_denoms: msg.blocked_denoms.clone(),
+        rate_guard: None,
+        max_total_weight: msg.max_total_weight,
+        freeze_delay: msg.freeze_delay,
+        proposal_ttl: msg.proposal_ttl,
+        max_admins: msg.max_admins,
+    })
+}
+
+// empty or whitespace-only strings are rejected up front instead of relying on addr_validate
+fn validate_admin_addr(deps: Deps, addr: &str) -> Result<Addr, ContractError> {
+    if addr.trim().is_empty() {
+        return Err(ContractError::InvalidAddress {
+            addr: addr.to_owned(),
+        });
+    }
+    Ok(deps.api.addr_validate(addr)?)
+}
+
+const DEFAULT_MAX_LABEL_LEN: u32 = 64;
+const CURRENT_STATE_VERSION: u32 = 1; // 1: ADMIN_COUNT re-derived from ADMINS
+const PAUSED_INDEFINITELY: Timestamp = Timestamp::from_nanos(u64::MAX); // maintenance_until set by the rate guard
+
+// every site that stores a label goes through here, so the limit is the same everywhere
+fn validate_label(deps: Deps, label: &str) -> Result<(), ContractError> {
+    let max = CONFIG.load(deps.storage)?.max_label_len;
+    if label.chars().count() > max as usize {
+        return Err(ContractError::LabelTooLong { max });
+    }
+    Ok(())
+}
+
+// native denom format from the bank module: 3 to 128 characters, a leading letter, then letters,
+// digits and any of `/:._-`
+fn validate_denom(denom: &str) -> Result<(), ContractError> {
+    let valid = (3..=128).contains(&denom.len())
+        && denom.starts_with(|c: char| c.is_ascii_alphabetic())
+        && denom
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c));
+    if !valid {
+        return Err(ContractError::InvalidDenom {
+            denom: denom.to_owned(),
+        });
+    }
+    Ok(())
+}
+
+// The checks DonateTo and instantiate run on the funds they received, PayoutPlan runs them on the
+// funds it's asked about. Funds sent along with a message never repeat a denom, a PayoutPlan query
+// could.
+fn validate_donation(deps: Deps, funds: &[Coin]) -> Result<(), ContractError> {
+    if funds.iter().all(|fund| fund.amount.is_zero()) { // also true for no funds at all
+        return Err(ContractError::EmptyDonation {});
+    }
+
+    let blocked_denoms = CONFIG.load(deps.storage)?.blocked_denoms;
+    for (i, fund) in funds.iter().enumerate() {
+        validate_denom(&fund.denom)?;
+        if funds[..i].iter().any(|other| other.denom == fund.denom) {
+            return Err(ContractError::DuplicateDenom {
+                denom: fund.denom.clone(),
+            });
+        }
+        if blocked_denoms.contains(&fund.denom) {
+            return Err(ContractError::DenomBlocked {
+                denom: fund.denom.clone(),
+            });
+        }
+        if let Some(min) = MIN_DONATIONS.may_load(deps.storage, &fund.denom)? {
+            if fund.amount < min {
+                return Err(ContractError::DonationTooSmall {
+                    denom: fund.denom.clone(),
+                    min,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+// Every path removing an admin on request goes through here, so with owner_must_be_admin set the
+// owner can't lose admin access by leaving or being removed.
+fn ensure_not_owner_removal(deps: Deps, addr: &Addr) -> Result<(), ContractError> {
+    if CONFIG.load(deps.storage)?.owner_must_be_admin && *addr == OWNER.load(deps.storage)? {
+        return Err(ContractError::OwnerMustBeAdmin {});
+    }
+    Ok(())
+}
+
+// Removes every expired admin but a required owner, for migrate's prune_expired and PokeExpiry.
+// Returns the removed admins in the canonical order.
+fn remove_expired(deps: DepsMut, env: &Env) -> Result<Vec<Addr>, ContractError> {
+    let mut removed = vec![];
+    for (addr, admin_info) in admins_ordered(deps.storage)? {
+        if is_active(&admin_info, env) {
+            continue;
+        }
+        match ensure_not_owner_removal(deps.as_ref(), &addr) {
+            Ok(()) => {
+                remove_admin_fully(deps.storage, deps.api, &addr)?;
+                removed.push(addr);
+            }
+            Err(ContractError::OwnerMustBeAdmin {}) => {} // a required owner stays even when expired
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(removed)
+}
+
+// whether `sender` is an active admin whose role has the capability picked by `allowed`
+fn has_capability(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    allowed: fn(&Capabilities) -> bool,
+) -> StdResult<bool> {
+    match ADMINS.may_load(deps.storage, sender)? {
+        Some(admin_info) if is_active(&admin_info, env) => {
+            Ok(allowed(&role_capabilities(deps.storage, admin_info.role)?))
+        }
+        _ => Ok(false),
+    }
+}
+
+// Like has_capability, but also true for the delegate of an admin with the capability until the
+// delegation set through Delegate runs out. Only add and remove rights can be delegated.
+fn has_delegated_capability(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    allowed: fn(&Capabilities) -> bool,
+) -> StdResult<bool> {
+    if has_capability(deps, env, sender, allowed)? {
+        return Ok(true);
+    }
+    Ok(delegating_admin(deps, env, sender, allowed)?.is_some())
+}
+
+// the active admin with the capability picked by `allowed` whose running delegation names `sender`
+fn delegating_admin(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    allowed: fn(&Capabilities) -> bool,
+) -> StdResult<Option<Addr>> {
+    for item in DELEGATIONS.range(deps.storage, None, None, Order::Ascending) {
+        let (admin, (delegate, until)) = item?;
+        if delegate == *sender
+            && env.block.time < until
+            && has_capability(deps, env, &admin, allowed)?
+        {
+            return Ok(Some(admin));
+        }
+    }
+    Ok(None)
+}
+
+// The admin `sender` acts for: themselves when they're an admin, otherwise the admin delegating
+// to them. A delegate shares that admin's cooldown and last action instead of getting their own.
+fn acting_admin(deps: Deps, env: &Env, sender: &Addr) -> StdResult<Option<Addr>> {
+    if ADMINS.has(deps.storage, sender) {
+        return Ok(Some(sender.clone()));
+    }
+    delegating_admin(deps, env, sender, |_| true)
+}
+
+// frozen for good by ExportRoster, or by ProposeFreeze once its freeze_at is reached
+fn is_frozen(deps: Deps, env: &Env) -> StdResult<bool> {
+    if FROZEN.may_load(deps.storage)?.unwrap_or_default() {
+        return Ok(true);
+    }
+    Ok(FREEZE_AT
+        .may_load(deps.storage)?
+        .is_some_and(|freeze_at| env.block.time >= freeze_at))
+}
+
+// the window set through SetMaintenance ends once the block time reaches `until`
+fn ensure_not_in_maintenance(deps: Deps, env: &Env) -> Result<(), ContractError> {
+    match CONFIG.load(deps.storage)?.maintenance_until {
+        Some(until) if env.block.time < until => Err(ContractError::Maintenance { until }),
+        _ => Ok(()),
+    }
+}
+
+// per_admin_cooldown is measured from the last exec recorded by record_action
+fn ensure_cooled_down(deps: Deps, env: &Env, sender: &Addr) -> Result<(), ContractError> {
+    let cooldown = match CONFIG.load(deps.storage)?.per_admin_cooldown {
+        Some(cooldown) => cooldown,
+        None => return Ok(()),
+    };
+    if let Some(last_action) = LAST_ACTION.may_load(deps.storage, sender)? {
+        let ready_at = last_action.plus_seconds(cooldown);
+        if env.block.time < ready_at {
+            return Err(ContractError::Cooldown { ready_at });
+        }
+    }
+    Ok(())
+}
+
+// records the block time of every exec sent by an admin or their delegate, which is what the
+// cooldown and InactiveSince/PruneInactive go by
+fn record_action(deps: DepsMut, env: &Env, sender: &Addr) -> StdResult<()> {
+    if let Some(admin) = acting_admin(deps.as_ref(), env, sender)? {
+        LAST_ACTION.save(deps.storage, &admin, &env.block.time)?;
+    }
+    Ok(())
+}
+
+// Pauses the contract until the owner ends it through SetMaintenance once more roster changes than
+// the rate guard allows happened within its window. The exec that crosses the limit still goes
+// through, every later one is rejected. Returns whether the contract was paused.
+fn guard_roster_rate(deps: DepsMut, now: Timestamp, changes_before: u64) -> StdResult<bool> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let guard = match config.rate_guard {
+        Some(guard) => guard,
+        None => return Ok(false),
+    };
+    let changes = ROSTER_CHANGES.may_load(deps.storage)?.unwrap_or_default() - changes_before;
+    if changes == 0 {
+        return Ok(false);
+    }
+
+    let mut recent = RECENT_CHANGES.may_load(deps.storage)?.unwrap_or_default();
+    recent.retain(|(at, _)| at.plus_seconds(guard.window) > now);
+    recent.push((now, changes));
+    let total: u64 = recent.iter().map(|(_, changes)| changes).sum();
+    RECENT_CHANGES.save(deps.storage, &recent)?;
+    if total <= guard.max_changes {
+        return Ok(false);
+    }
+
+    config.maintenance_until = Some(PAUSED_INDEFINITELY);
+    CONFIG.save(deps.storage, &config)?;
+    Ok(true)
+}
+
+// Admins whose last exec, or their join time if they never sent one, is at least `duration`
+// seconds old, in the canonical admin ordering.
+fn inactive_admins(storage: &dyn Storage, env: &Env, duration: u64) -> StdResult<Vec<Addr>> {
+    let mut inactive = vec![];
+    for (addr, admin_info) in admins_ordered(storage)? {
+        let last_action = LAST_ACTION
+            .may_load(storage, &addr)?
+            .unwrap_or(admin_info.joined);
+        if last_action.plus_seconds(duration) <= env.block.time {
+            inactive.push(addr);
+        }
+    }
+    Ok(inactive)
+}
+
+// Proposals older than proposal_ttl are ignored everywhere until PruneProposals drops them.
+// Proposals without a recorded time were made before proposal_ttl existed and never expire.
+fn is_proposal_expired(config: &Config, env: &Env, proposed_at: Option<Timestamp>) -> bool {
+    match (config.proposal_ttl, proposed_at) {
+        (Some(ttl), Some(proposed_at)) => proposed_at.plus_seconds(ttl) <= env.block.time,
+        _ => false,
+    }
+}
+
+// addresses in `proposed_at` (PROPOSED_AT or REMOVAL_PROPOSED_AT) whose proposal has expired
+fn expired_proposals(
+    storage: &dyn Storage,
+    env: &Env,
+    proposed_at: &Map<&Addr, Timestamp>,
+) -> StdResult<Vec<Addr>> {
+    let config = CONFIG.load(storage)?;
+    let mut expired = vec![];
+    for item in proposed_at.range(storage, None, None, Order::Ascending) {
+        let (addr, at) = item?;
+        if is_proposal_expired(&config, env, Some(at)) {
+            expired.push(addr);
+        }
+    }
+    Ok(expired)
+}
+
+// Label, annotation and rank updates pass the version of the admin they were based on, so of two
+// updates based on the same version only the first goes through. Returns the updated admin.
+fn bump_version(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    expected: u64,
+) -> Result<AdminInfo, ContractError> {
+    let mut admin_info = ADMINS
+        .may_load(storage, addr)?
+        .ok_or_else(|| ContractError::NotAdmin { addr: addr.clone() })?;
+    if admin_info.version != expected {
+        return Err(ContractError::StaleUpdate {
+            expected,
+            actual: admin_info.version,
+        });
+    }
+    admin_info.version += 1;
+    ADMINS.save(storage, addr, &admin_info)?;
+    Ok(admin_info)
+}
+
+// an admin with their label and annotations, as carried by ExportRoster and the Export query
+fn admin_record(
+    storage: &dyn Storage,
+    addr: Addr,
+    admin_info: AdminInfo,
+) -> StdResult<AdminRecord> {
+    let label = LABELS.may_load(storage, &addr)?;
+    let annotations = ANNOTATIONS
+        .prefix(&addr)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    Ok(AdminRecord {
+        addr: addr.into_string(),
+        joined: admin_info.joined,
+        role: admin_info.role,
+        weight: admin_info.weight,
+        expires_at: admin_info.expires_at,
+        added_by: admin_info.added_by.into_string(),
+        label,
+        annotations,
+        rank: admin_info.rank,
+        version: admin_info.version,
+        joined_height: admin_info.joined_height,
+    })
+}
+
+// Checked after every exec instead of in each path that can add weight (AddMembers, invites,
+// AcceptAdmin, ImportRoster), an error reverts the whole exec. The sum is checked so it can't wrap.
+fn ensure_weight_cap(deps: Deps) -> Result<(), ContractError> {
+    let max = match CONFIG.load(deps.storage)?.max_total_weight {
+        Some(max) => max,
+        None => return Ok(()),
+    };
+    let mut total: u64 = 0;
+    for item in ADMINS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, admin_info) = item?;
+        total = total
+            .checked_add(admin_info.weight)
+            .ok_or(ContractError::WeightCapExceeded { max })?;
+    }
+    if total > max {
+        return Err(ContractError::WeightCapExceeded { max });
+    }
+    Ok(())
+}
+
+// checked at the same points as ensure_weight_cap, against the cached ADMIN_COUNT
+fn ensure_admin_cap(deps: Deps) -> Result<(), ContractError> {
+    match CONFIG.load(deps.storage)?.max_admins {
+        Some(max) if ADMIN_COUNT.load(deps.storage)? > max => {
+            Err(ContractError::TooManyAdmins { max })
+        }
+        _ => Ok(()),
+    }
+}
+
+// Names of the fields `other` differs in from `config`, used by ConfigMatches and the
+// config_changed event.
+fn config_diff(config: &Config, other: Config) -> Vec<String> {
+    // destructured so a new config field can't be left out of the comparison
+    let Config {
+        log_contract,
+        greeting_locked,
+        strict_limits,
+        cw4_group,
+        min_delegation,
+        default_role,
+        transfer_delay,
+        min_tenure,
+        maintenance_until,
+        migration_source,
+        per_admin_cooldown,
+        allowlist_contract,
+        require_acceptance,
+        fallback_admin,
+        max_label_len,
+        notify_on_join,
+        removal_threshold,
+        owner_must_be_admin,
+        blocked_denoms,
+        rate_guard,
+        max_total_weight,
+        freeze_delay,
+        proposal_ttl,
+        max_admins,
+    } = other;
+    let fields = [
+        ("log_contract", log_contract == config.log_contract),
+        ("greeting_locked", greeting_locked == config.greeting_locked),
+        ("strict_limits", strict_limits == config.strict_limits),
+        ("cw4_group", cw4_group == config.cw4_group),
+        ("min_delegation", min_delegation == config.min_delegation),
+        ("default_role", default_role == config.default_role),
+        ("transfer_delay", transfer_delay == config.transfer_delay),
+        ("min_tenure", min_tenure == config.min_tenure),
+        (
+            "maintenance_until",
+            maintenance_until == config.maintenance_until,
+        ),
+        (
+            "migration_source",
+            migration_source == config.migration_source,
+        ),
+        (
+            "per_admin_cooldown",
+            per_admin_cooldown == config.per_admin_cooldown,
+        ),
+        (
+            "allowlist_contract",
+            allowlist_contract == config.allowlist_contract,
+        ),
+        (
+            "require_acceptance",
+            require_acceptance == config.require_acceptance,
+        ),
+        ("fallback_admin", fallback_admin == config.fallback_admin),
+        ("max_label_len", max_label_len == config.max_label_len),
+        ("notify_on_join", notify_on_join == config.notify_on_join),
+        (
+            "removal_threshold",
+            removal_threshold == config.removal_threshold,
+        ),
+        (
+            "owner_must_be_admin",
+            owner_must_be_admin == config.owner_must_be_admin,
+        ),
+        ("blocked_denoms", blocked_denoms == config.blocked_denoms),
+        ("rate_guard", rate_guard == config.rate_guard),
+        (
+            "max_total_weight",
+            max_total_weight == config.max_total_weight,
+        ),
+        ("freeze_delay", freeze_delay == config.freeze_delay),
+        ("proposal_ttl", proposal_ttl == config.proposal_ttl),
+        ("max_admins", max_admins == config.max_admins),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, matches)| !matches)
+        .map(|(field, _)| field.to_owned())
+        .collect()
+}
+
+// Emitted by execute whenever an exec changed the config, with one "field" attribute per
+// changed field, so config drift can be followed from events alone.
+fn config_changed_event(old: &Config, new: &Config) -> Option<Event> {
+    let fields = config_diff(old, new.clone());
+    if fields.is_empty() {
+        return None;
+    }
+    Some(
+        Event::new("config_changed")
+            .add_attributes(fields.into_iter().map(|field| ("field", field))),
+    )
+}
+
+// Splits `funds` equally between `recipients`, one send each. Whatever doesn't divide evenly, or
+// everything if there are no recipients, stays in the contract where the owner can sweep it.
+fn split_funds(funds: &[Coin], recipients: &[Addr]) -> Vec<BankMsg> {
+    let share = split_share(funds, recipients.len());
+    if share.is_empty() {
+        return vec![];
+    }
+
+    recipients
+        .iter()
+        .map(|addr| BankMsg::Send {
+            to_address: addr.to_string(),
+            amount: share.clone(),
+        })
+        .collect()
+}
+
+// what each of `recipients` gets from `funds`, denoms too small to reach everyone are left out
+fn split_share(funds: &[Coin], recipients: usize) -> Vec<Coin> {
+    if recipients == 0 {
+        return vec![];
+    }
+
+    let count = Uint128::from(recipients as u128);
+    funds
+        .iter()
+        .map(|fund| Coin {
+            denom: fund.denom.clone(),
+            amount: fund.amount / count,
+        })
+        .filter(|fund| !fund.amount.is_zero())
+        .collect()
+}
+
+// every admin of `other`, another instance of this contract, paging through its AdminsList
+// until a page comes back empty
+fn other_admins(deps: Deps, other: &Addr) -> StdResult<Vec<Addr>> {
+    let mut admins: Vec<Addr> = vec![];
+    loop {
+        let page: AdminsListResp = deps.querier.query_wasm_smart(
+            other,
+            &QueryMsg::AdminsList {
+                start_after: admins.last().map(|addr| addr.to_string()),
+                limit: None,
+            },
+        )?;
+        if page.admins.is_empty() {
+            return Ok(admins);
+        }
+        admins.extend(page.admins);
+    }
+}
+
+// one welcome message per newly added admin for the configured notifier, if there is one
+fn welcome_msgs<'a>(
+    deps: Deps,
+    added: impl IntoIterator<Item = &'a Addr>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let notifier = match CONFIG.load(deps.storage)?.notify_on_join {
+        Some(notifier) => notifier,
+        None => return Ok(vec![]),
+    };
+
+    added
         .into_iter()
-        .map(|addr| deps.api.addr_validate(&addr))
-        .collect();
-    ADMINS.save(deps.storage, &admins?)?;
+        .map(|admin| {
+            let msg = WasmMsg::Execute {
+                contract_addr: notifier.to_string(),
+                msg: to_binary(&NotifierExecuteMsg::Welcome {
+                    admin: admin.clone(),
+                })?,
+                funds: vec![],
+            };
+            Ok(msg.into())
+        })
+        .collect()
+}
+
+// builds the membership update for the mirrored cw4 group, if one is configured and anything changed
+fn cw4_update_msg(
+    deps: Deps,
+    remove: Vec<Addr>,
+    add: Vec<(Addr, u64)>,
+) -> StdResult<Option<CosmosMsg>> {
+    let cw4_group = match CONFIG.load(deps.storage)?.cw4_group {
+        Some(cw4_group) => cw4_group,
+        None => return Ok(None),
+    };
+    if remove.is_empty() && add.is_empty() {
+        return Ok(None);
+    }
 
-    Ok(Response::new())
+    let msg = WasmMsg::Execute {
+        contract_addr: cw4_group.into_string(),
+        msg: to_binary(&Cw4ExecuteMsg::UpdateMembers {
+            remove: remove.into_iter().map(Addr::into_string).collect(),
+            add: add
+                .into_iter()
+                .map(|(addr, weight)| Cw4Member {
+                    addr: addr.into_string(),
+                    weight,
+                })
+                .collect(),
+        })?,
+        funds: vec![],
+    };
+    Ok(Some(msg.into()))
 }
 
 
@@ -44,214 +749,9755 @@ pub fn instantiate(
 // Queries are supposed just purely to return some transformed contract state. It can be calculated based on some chain metadata 
 // (so the state can "automatically" change after some time), but not on message info
 
-// pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary>  { // Returns an aribitrary binary serializable object becuase queries cannot trigger any actions nor communicate with other contracts in ways different than querying them
-//     use QueryMsg::*;
+// pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary>  { // Returns an aribitrary binary serializable object becuase queries cannot trigger any actions nor communicate with other contracts in ways different than querying them
+//     use QueryMsg::*;
+
+//     match msg {
+//         Greet {} => to_binary(&query::greet()?),
+//     }
+// }
+
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    use QueryMsg::*;
+
+    match msg {
+        Greet {} => to_binary(&query::greet(deps)?),
+        AdminsList { start_after, limit } => {
+            to_binary(&query::admins_list(deps, start_after, limit)?)
+        }
+        Reconcile { expected } => to_binary(&query::reconcile(deps, expected)?),
+        AdminsPreview { limit } => to_binary(&query::admins_preview(deps, limit)?),
+        Invites { start_after, limit } => to_binary(&query::invites(deps, start_after, limit)?),
+        Annotations { addr } => to_binary(&query::annotations(deps, addr)?),
+        AdminsByRole {} => to_binary(&query::admins_by_role(deps)?),
+        IndexOf { addr } => to_binary(&query::index_of(deps, addr)?),
+        StateFingerprint {} => to_binary(&query::state_fingerprint(deps)?),
+        PendingOwnerReadyAt {} => to_binary(&query::pending_owner_ready_at(deps)?),
+        NextExpiry {} => to_binary(&query::next_expiry(deps, env)?),
+        ExpiringSoon { within_blocks } => {
+            to_binary(&query::expiring_soon(deps, env, within_blocks)?)
+        }
+        BothAdmins { a, b } => to_binary(&query::both_admins(deps, a, b)?),
+        CreatedAt {} => to_binary(&query::created_at(deps)?),
+        Availability {} => to_binary(&query::availability(deps, env)?),
+        CheckIntegrity {} => to_binary(&query::check_integrity(deps)?),
+        PendingSelfAccept {} => to_binary(&query::pending_self_accept(deps, env)?),
+        AddedBy { addr } => to_binary(&query::added_by(deps, addr)?),
+        RemovableBy { sender } => to_binary(&query::removable_by(deps, env, sender)?),
+        BlockInfo {} => to_binary(&query::block_info(env)),
+        CanExecute { sender, msg } => to_binary(&query::can_execute(deps, env, sender, msg)?),
+        CanJoin { addr } => to_binary(&query::can_join(deps, env, addr)?),
+        SelfAddress {} => to_binary(&query::self_address(env)),
+        ConfigMatches { expected } => to_binary(&query::config_matches(deps, *expected)?),
+        Delegations {} => to_binary(&query::delegations(deps)?),
+        Intersection { addrs } => to_binary(&query::intersection(deps, addrs)?),
+        DiffFrom { other } => to_binary(&query::diff_from(deps, other)?),
+        Export { start_after, limit } => to_binary(&query::export(deps, start_after, limit)?),
+        EffectiveAuthority { sender } => to_binary(&query::effective_authority(deps, env, sender)?),
+        PayoutPlan { funds } => to_binary(&query::payout_plan(deps, env, funds)?),
+        FreezeStatus {} => to_binary(&query::freeze_status(deps, env)?),
+        StatusCounts {} => to_binary(&query::status_counts(deps, env)?),
+        IsBricked {} => to_binary(&query::is_bricked(deps)?),
+        AdminsAddedBetween {
+            from_height,
+            to_height,
+        } => to_binary(&query::admins_added_between(deps, from_height, to_height)?),
+        Policy {} => to_binary(&query::policy(deps, env)?),
+        TotalDonated {} => to_binary(&query::total_donated(deps)?),
+        Count {} => to_binary(&query::count(deps)?),
+        CountExact {} => to_binary(&query::count_exact(deps)?),
+        AdminsByRank {} => to_binary(&query::admins_by_rank(deps)?),
+        PendingRemovals {} => to_binary(&query::pending_removals(deps, env)?),
+        PrunableProposals {} => to_binary(&query::prunable_proposals(deps, env)?),
+        InactiveSince { duration } => to_binary(&query::inactive_since(deps, env, duration)?),
+        AdminsByMinWeight {
+            min,
+            start_after,
+            limit,
+        } => to_binary(&query::admins_by_min_weight(deps, min, start_after, limit)?),
+    }
+}
+
+
+pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    exec::authorize(deps.as_ref(), &env, &info.sender, &msg)?;
+    record_action(deps.branch(), &env, &info.sender)?;
+
+    let changes_before = ROSTER_CHANGES.may_load(deps.storage)?.unwrap_or_default();
+    let config_before = CONFIG.load(deps.storage)?;
+    let now = env.block.time;
+    let mut resp = dispatch(deps.branch(), env, info, msg)?;
+    ensure_weight_cap(deps.as_ref())?;
+    ensure_admin_cap(deps.as_ref())?;
+    if guard_roster_rate(deps.branch(), now, changes_before)? {
+        resp = resp.add_attribute("auto_paused", "true");
+    }
+    if let Some(event) = config_changed_event(&config_before, &CONFIG.load(deps.storage)?) {
+        resp = resp.add_event(event);
+    }
+    Ok(resp)
+}
+
+fn dispatch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    use ExecuteMsg::*;
+
+    match msg {
+        AddMembers { admins } => exec::add_members(deps, env, info, admins),
+        RemoveMembers { admins } => exec::remove_members(deps, env, info, admins),
+        Leave {} => exec::leave(deps, env, info),
+        SetGreeting { greeting } => exec::set_greeting(deps, info, greeting),
+        Sweep { denom, to } => exec::sweep(deps, env, info, denom, to),
+        CreateInvite {
+            code_hash,
+            role,
+            weight,
+            expires_at,
+        } => exec::create_invite(deps, info, code_hash, role, weight, expires_at),
+        RedeemInvite { code } => exec::redeem_invite(deps, env, info, code),
+        SetAnnotation {
+            addr,
+            key,
+            value,
+            expected_version,
+        } => exec::set_annotation(deps, info, addr, key, value, expected_version),
+        ClearAnnotations {
+            addr,
+            expected_version,
+        } => exec::clear_annotations(deps, info, addr, expected_version),
+        UpdateConfig { default_role } => exec::update_config(deps, info, default_role),
+        TransferOwnership { new_owner } => exec::transfer_ownership(deps, env, info, new_owner),
+        AcceptOwnership {} => exec::accept_ownership(deps, env, info),
+        ExtendExpiry {
+            addr,
+            new_expires_at,
+        } => exec::extend_expiry(deps, info, addr, new_expires_at),
+        SetMaintenance { until } => exec::set_maintenance(deps, info, until),
+        ExportRoster { to, freeze } => exec::export_roster(deps, env, info, to, freeze),
+        ImportRoster { admins } => exec::import_roster(deps, info, admins),
+        DonateTo { recipients } => exec::donate_to(deps, env, info, recipients),
+        ProposeAdmin { addr } => exec::propose_admin(deps, env, info, addr),
+        AcceptAdmin {} => exec::accept_admin(deps, env, info),
+        SetLabel {
+            addr,
+            label,
+            expected_version,
+        } => exec::set_label(deps, info, addr, label, expected_version),
+        SetMyLabel {
+            label,
+            expected_version,
+        } => exec::set_my_label(deps, info, label, expected_version),
+        OwnerRemove { addr } => exec::owner_remove(deps, info, addr),
+        SwapRoles { a, b } => exec::swap_roles(deps, info, a, b),
+        PruneInactive { duration } => exec::prune_inactive(deps, env, info, duration),
+        ProposeRemove { addr } => exec::propose_remove(deps, env, info, addr),
+        ApproveRemove { addr } => exec::approve_remove(deps, env, info, addr),
+        SetRank {
+            addr,
+            rank,
+            expected_version,
+        } => exec::set_rank(deps, info, addr, rank, expected_version),
+        SetRoleCapabilities { role, capabilities } => {
+            exec::set_role_capabilities(deps, info, role, capabilities)
+        }
+        SetRateGuard {
+            max_changes,
+            window,
+        } => exec::set_rate_guard(deps, info, max_changes, window),
+        Delegate { to, until } => exec::delegate(deps, env, info, to, until),
+        RevokeDelegation {} => exec::revoke_delegation(deps, info),
+        ProposeFreeze {} => exec::propose_freeze(deps, env, info),
+        CancelFreeze {} => exec::cancel_freeze(deps, info),
+        MergeFrom { other } => exec::merge_from(deps, env, info, other),
+        SetLabels { entries } => exec::set_labels(deps, info, entries),
+        Reorder { order } => exec::reorder(deps, info, order),
+        PruneProposals {} => exec::prune_proposals(deps, env, info),
+        PokeExpiry {} => exec::poke_expiry(deps, env, info),
+    }
+}
+
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::SetReadOnly { read_only } => {
+            READ_ONLY.save(deps.storage, &read_only)?;
+            Ok(Response::new().add_attribute("read_only", read_only.to_string()))
+        }
+        SudoMsg::Recover { admins } => recover(deps, env, admins),
+    }
+}
+
+// Governance's way out of a bricked contract, an empty roster can't be refilled through any exec.
+// Recovered admins are recorded as added by the contract itself and are held to the same caps as
+// any other roster change.
+fn recover(deps: DepsMut, env: Env, admins: Vec<String>) -> Result<Response, ContractError> {
+    if ADMIN_COUNT.load(deps.storage)? > 0 {
+        return Err(ContractError::AlreadyPopulated {});
+    }
+
+    let admin_info = AdminInfo::new(&env.block, env.contract.address.clone());
+    let mut added = vec![];
+    for addr in admins {
+        let addr = validate_admin_addr(deps.as_ref(), &addr)?;
+        if add_admin(deps.storage, deps.api, &addr, &admin_info)? {
+            added.push((addr, admin_info.weight));
+        }
+    }
+    added.extend(exec::activate_fallback(deps.storage, deps.api, &env)?); // recovering nobody still brings in the fallback admin
+    ensure_weight_cap(deps.as_ref())?;
+    ensure_admin_cap(deps.as_ref())?;
+
+    let count = added.len();
+    let welcome = welcome_msgs(deps.as_ref(), added.iter().map(|(addr, _)| addr))?;
+    let cw4 = cw4_update_msg(deps.as_ref(), vec![], added)?;
+    Ok(Response::new()
+        .add_messages(cw4)
+        .add_messages(welcome)
+        .add_attribute("recovered", count.to_string()))
+}
+
+// the original contract only stored its admins as one list under "admins", this moves them into ADMINS
+// and stores what instantiate would have set up, with every optional feature left off
+fn migrate_legacy_admins(
+    deps: DepsMut,
+    env: &Env,
+    legacy: Vec<Addr>,
+) -> Result<u32, ContractError> {
+    let config = instantiate_config(deps.as_ref(), &InstantiateMsg::default())?;
+    CONFIG.save(deps.storage, &config)?;
+    CREATED.save(deps.storage, &(env.block.height, env.block.time))?;
+    GREETING.save(deps.storage, &"Hello World".to_owned())?;
+    ADMIN_COUNT.save(deps.storage, &0)?;
+
+    let admin_info = AdminInfo::new(&env.block, env.contract.address.clone());
+    let mut added = 0;
+    for addr in legacy {
+        if add_admin(deps.storage, deps.api, &addr, &admin_info)? {
+            added += 1;
+        }
+    }
+    LEGACY_ADMINS.remove(deps.storage);
+    Ok(added)
+}
+
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let mut resp = Response::new();
+    if let Some(legacy) = LEGACY_ADMINS.may_load(deps.storage)? {
+        let converted = migrate_legacy_admins(deps.branch(), &env, legacy)?;
+        resp = resp.add_attribute("converted", converted.to_string());
+    }
+
+    // deployments from before the owner feature have no OWNER stored, an existing owner is left as is
+    if OWNER.may_load(deps.storage)?.is_none() {
+        let owner = msg.owner.ok_or(ContractError::OwnerRequired {})?;
+        let owner = validate_admin_addr(deps.as_ref(), &owner)?;
+        if !msg.allow_non_admin_owner && !ADMINS.has(deps.storage, &owner) {
+            return Err(ContractError::NotAdmin { addr: owner });
+        }
+        OWNER.save(deps.storage, &owner)?;
+    }
+
+    let version = STATE_VERSION.may_load(deps.storage)?.unwrap_or_default();
+    if version < 1 { // counters from before version 1 may have drifted, ADMINS is the source of truth
+        let count = ADMINS
+            .keys_raw(deps.storage, None, None, Order::Ascending)
+            .count() as u32;
+        ADMIN_COUNT.save(deps.storage, &count)?;
+        resp = resp.add_attribute("admin_count", count.to_string());
+    }
+    STATE_VERSION.save(deps.storage, &CURRENT_STATE_VERSION)?;
+
+    if !msg.prune_expired {
+        return Ok(resp);
+    }
+    let pruned = remove_expired(deps.branch(), &env)?;
+    let fallback = exec::activate_fallback(deps.storage, deps.api, &env)?;
+
+    let count = pruned.len();
+    let welcome = welcome_msgs(deps.as_ref(), fallback.iter().map(|(addr, _)| addr))?;
+    let cw4 = cw4_update_msg(deps.as_ref(), pruned, fallback.into_iter().collect())?;
+    Ok(resp
+        .add_messages(cw4)
+        .add_messages(welcome)
+        .add_attribute("pruned", count.to_string()))
+}
+
+mod exec {
+    use super::*;
+
+    // builds the audit message for the log contract, if one is configured
+    fn log_msg(
+        deps: Deps,
+        action: &str,
+        sender: &Addr,
+        affected: Vec<Addr>,
+    ) -> StdResult<Option<CosmosMsg>> {
+        let config = CONFIG.load(deps.storage)?;
+        let log_contract = match config.log_contract {
+            Some(log_contract) => log_contract,
+            None => return Ok(None),
+        };
+
+        let msg = WasmMsg::Execute {
+            contract_addr: log_contract.into_string(),
+            msg: to_binary(&LogExecuteMsg::Log {
+                action: action.to_owned(),
+                sender: sender.clone(),
+                affected,
+            })?,
+            funds: vec![],
+        };
+        Ok(Some(msg.into()))
+    }
+
+    // the sender must be an admin that hasn't expired yet
+    fn ensure_admin(deps: Deps, env: &Env, sender: &Addr) -> Result<AdminInfo, ContractError> {
+        match ADMINS.may_load(deps.storage, sender)? {
+            Some(admin_info) if is_active(&admin_info, env) => Ok(admin_info),
+            _ => Err(ContractError::Unauthorized {
+                sender: sender.clone(),
+            }),
+        }
+    }
+
+    // new admins need at least `min_delegation` staked when the contract requires it
+    pub fn ensure_min_delegation(deps: Deps, addr: &Addr) -> Result<(), ContractError> {
+        let min_delegation = match CONFIG.load(deps.storage)?.min_delegation {
+            Some(min_delegation) => min_delegation,
+            None => return Ok(()),
+        };
+
+        // only stake in the chain's bonding denom counts
+        let bonded_denom = deps.querier.query_bonded_denom()?;
+        let staked: Uint128 = deps
+            .querier
+            .query_all_delegations(addr)?
+            .into_iter()
+            .filter(|delegation| delegation.amount.denom == bonded_denom)
+            .map(|delegation| delegation.amount.amount)
+            .sum();
+        if staked < min_delegation {
+            return Err(ContractError::InsufficientStake {});
+        }
+        Ok(())
+    }
+
+    pub fn ensure_allowlisted(deps: Deps, addr: &Addr) -> Result<(), ContractError> {
+        let allowlist_contract = match CONFIG.load(deps.storage)?.allowlist_contract {
+            Some(allowlist_contract) => allowlist_contract,
+            None => return Ok(()),
+        };
+
+        let resp: IsAllowedResp = deps.querier.query_wasm_smart(
+            allowlist_contract,
+            &AllowlistQueryMsg::IsAllowed {
+                addr: addr.to_string(),
+            },
+        )?;
+        if !resp.allowed {
+            return Err(ContractError::NotAllowlisted { addr: addr.clone() });
+        }
+        Ok(())
+    }
+
+    // every exec that brings in a new admin runs both checks, whoever triggers it
+    fn ensure_eligible(deps: Deps, addr: &Addr) -> Result<(), ContractError> {
+        ensure_min_delegation(deps, addr)?;
+        ensure_allowlisted(deps, addr)
+    }
+
+    fn ensure_owner(deps: Deps, sender: &Addr) -> Result<(), ContractError> {
+        if OWNER.load(deps.storage)? != *sender {
+            return Err(ContractError::NotOwner {
+                sender: sender.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    // the owner can always remove, admins when their role has can_remove
+    fn ensure_can_remove(deps: Deps, env: &Env, sender: &Addr) -> Result<(), ContractError> {
+        if OWNER.load(deps.storage)? == *sender
+            || has_delegated_capability(deps, env, sender, |caps| caps.can_remove)?
+        {
+            return Ok(());
+        }
+        Err(ContractError::Unauthorized {
+            sender: sender.clone(),
+        })
+    }
+
+    fn ensure_owner_or_super(deps: Deps, env: &Env, sender: &Addr) -> Result<(), ContractError> {
+        if OWNER.load(deps.storage)? == *sender {
+            return Ok(());
+        }
+        match ensure_admin(deps, env, sender)?.role {
+            Role::Super => Ok(()),
+            _ => Err(ContractError::Unauthorized {
+                sender: sender.clone(),
+            }),
+        }
+    }
+
+    // the gates that stop `msg` for every sender alike, authorize runs them first
+    pub fn ensure_available(deps: Deps, env: &Env, msg: &ExecuteMsg) -> Result<(), ContractError> {
+        if is_frozen(deps, env)? { // the roster was exported or a freeze took effect, this contract stays read-only
+            return Err(ContractError::Frozen {});
+        }
+        if READ_ONLY.may_load(deps.storage)?.unwrap_or_default() {
+            return Err(ContractError::ReadOnly {});
+        }
+        if !matches!(msg, ExecuteMsg::SetMaintenance { .. }) { // the owner has to be able to end the window early
+            ensure_not_in_maintenance(deps, env)?;
+        }
+        Ok(())
+    }
+
+    // config settings that switch `msg` off for everyone, authorize runs them after the sender's rules
+    pub fn ensure_enabled(deps: Deps, msg: &ExecuteMsg) -> Result<(), ContractError> {
+        use ExecuteMsg::*;
+
+        let config = CONFIG.load(deps.storage)?;
+        match msg {
+            // merged admins never agreed to join this contract either
+            AddMembers { .. } | MergeFrom { .. } if config.require_acceptance => {
+                Err(ContractError::AcceptanceRequired {})
+            }
+            RemoveMembers { .. } if config.removal_threshold.is_some() => {
+                Err(ContractError::ApprovalRequired {})
+            }
+            // locked at instantiate, nobody can change it
+            SetGreeting { .. } if config.greeting_locked => Err(ContractError::Frozen {}),
+            _ => Ok(()),
+        }
+    }
+
+    // Every check on whether `sender` may send `msg` right now runs here before the exec itself,
+    // and the CanExecute query runs the same function, so the two can't drift apart. That includes
+    // config settings that switch a message off for everyone, and who may remove each target of
+    // RemoveMembers, which RemovableBy relies on. Other rules on the targets of a message are
+    // still checked by the exec.
+    pub fn authorize(
+        deps: Deps,
+        env: &Env,
+        sender: &Addr,
+        msg: &ExecuteMsg,
+    ) -> Result<(), ContractError> {
+        use ExecuteMsg::*;
+
+        ensure_available(deps, env, msg)?;
+        if let Some(admin) = acting_admin(deps, env, sender)? { // admins and their delegates are throttled
+            ensure_cooled_down(deps, env, &admin)?;
+        }
+
+        match msg {
+            AddMembers { .. } | ProposeAdmin { .. } => {
+                if !has_delegated_capability(deps, env, sender, |caps| caps.can_add)? {
+                    return Err(ContractError::Unauthorized {
+                        sender: sender.clone(),
+                    });
+                }
+                Ok(())
+            }
+            RemoveMembers { admins } => {
+                ensure_can_remove(deps, env, sender)?;
+                let owner = OWNER.load(deps.storage)?;
+                for addr in admins {
+                    let addr = deps.api.addr_validate(addr)?;
+                    if addr == owner && *sender != owner { // only the owner can remove themself
+                        return Err(ContractError::Unauthorized {
+                            sender: sender.clone(),
+                        });
+                    }
+                    ensure_not_owner_removal(deps, &addr)?;
+                }
+                Ok(())
+            }
+            ProposeRemove { .. } | ApproveRemove { .. } => ensure_can_remove(deps, env, sender),
+            Leave {} => {
+                // a sender who isn't an admin has nothing to leave, the exec does nothing then
+                if let Some(admin_info) = ADMINS.may_load(deps.storage, sender)? {
+                    ensure_not_owner_removal(deps, sender)?;
+                    if let Some(min_tenure) = CONFIG.load(deps.storage)?.min_tenure {
+                        let ready_at = admin_info.joined.plus_seconds(min_tenure);
+                        if env.block.time < ready_at {
+                            return Err(ContractError::TenureNotMet { ready_at });
+                        }
+                    }
+                }
+                Ok(())
+            }
+            RedeemInvite { .. } | DonateTo { .. } | PokeExpiry {} => Ok(()),
+            SetGreeting { .. } | SetMyLabel { .. } | Delegate { .. } | RevokeDelegation {} => {
+                ensure_admin(deps, env, sender).map(|_| ())
+            }
+            Sweep { .. }
+            | TransferOwnership { .. }
+            | ExportRoster { .. }
+            | OwnerRemove { .. }
+            | PruneInactive { .. }
+            | SetRoleCapabilities { .. }
+            | SetRateGuard { .. }
+            | SetLabels { .. }
+            | Reorder { .. }
+            | ProposeFreeze {}
+            | CancelFreeze {}
+            | MergeFrom { .. } => ensure_owner(deps, sender),
+            CreateInvite { .. } => {
+                // an invite can carry any role and weight, so moderators can't create one, and it
+                // adds an admin, so supers need can_add as well
+                ensure_owner_or_super(deps, env, sender)?;
+                if OWNER.load(deps.storage)? != *sender
+                    && !has_capability(deps, env, sender, |caps| caps.can_add)?
+                {
+                    return Err(ContractError::Unauthorized {
+                        sender: sender.clone(),
+                    });
+                }
+                Ok(())
+            }
+            ExtendExpiry { .. }
+            | SetLabel { .. }
+            | SetAnnotation { .. }
+            | SwapRoles { .. }
+            | SetRank { .. }
+            | PruneProposals {} => ensure_owner_or_super(deps, env, sender),
+            ClearAnnotations { addr, .. } => {
+                // the admin themselves or the owner
+                if deps.api.addr_validate(addr)? != *sender {
+                    ensure_owner(deps, sender)?;
+                }
+                Ok(())
+            }
+            UpdateConfig { .. } => {
+                if !has_capability(deps, env, sender, |caps| caps.can_config)? {
+                    ensure_owner(deps, sender)?;
+                }
+                Ok(())
+            }
+            SetMaintenance { .. } => {
+                if !has_capability(deps, env, sender, |caps| caps.can_pause)? {
+                    ensure_owner(deps, sender)?;
+                }
+                Ok(())
+            }
+            AcceptOwnership {} => {
+                let pending = PENDING_OWNER
+                    .may_load(deps.storage)?
+                    .ok_or(ContractError::NoPendingOwner {})?;
+                if pending.addr != *sender {
+                    return Err(ContractError::NotPendingOwner {
+                        sender: sender.clone(),
+                    });
+                }
+                let ready_at = pending
+                    .proposed_at
+                    .plus_seconds(CONFIG.load(deps.storage)?.transfer_delay);
+                if env.block.time < ready_at {
+                    return Err(ContractError::TransferNotReady { ready_at });
+                }
+                Ok(())
+            }
+            ImportRoster { .. } => {
+                if CONFIG.load(deps.storage)?.migration_source.as_ref() != Some(sender) {
+                    return Err(ContractError::Unauthorized {
+                        sender: sender.clone(),
+                    });
+                }
+                Ok(())
+            }
+            AcceptAdmin {} => {
+                // only the proposed address itself can consent
+                let proposed_by = match PROPOSED_ADMINS.may_load(deps.storage, sender)? {
+                    Some(proposed_by) => proposed_by,
+                    None => {
+                        return Err(ContractError::NotProposed {
+                            sender: sender.clone(),
+                        })
+                    }
+                };
+                let proposed_at = PROPOSED_AT.may_load(deps.storage, sender)?;
+                if is_proposal_expired(&CONFIG.load(deps.storage)?, env, proposed_at) {
+                    return Err(ContractError::ProposalExpired {
+                        addr: sender.clone(),
+                    });
+                }
+                // the proposal only stands while whoever made it could still make it
+                if !has_delegated_capability(deps, env, &proposed_by, |caps| caps.can_add)? {
+                    return Err(ContractError::ProposerInactive {
+                        addr: sender.clone(),
+                        proposer: proposed_by,
+                    });
+                }
+                Ok(())
+            }
+        }?;
+
+        ensure_enabled(deps, msg)
+    }
+
+    // re-populates an emptied roster with the configured fallback admin, returned as a cw4 member
+    pub fn activate_fallback(
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        env: &Env,
+    ) -> StdResult<Option<(Addr, u64)>> {
+        let fallback_admin = match CONFIG.load(storage)?.fallback_admin {
+            Some(fallback_admin) => fallback_admin,
+            None => return Ok(None),
+        };
+        if ADMIN_COUNT.load(storage)? > 0 {
+            return Ok(None);
+        }
+
+        let admin_info = AdminInfo::new(&env.block, env.contract.address.clone());
+        add_admin(storage, api, &fallback_admin, &admin_info)?;
+        Ok(Some((fallback_admin, admin_info.weight)))
+    }
+
+    pub fn add_members(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        admins: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let admin_info = AdminInfo { // string-only additions get the configured default role
+            role: config.default_role,
+            ..AdminInfo::new(&env.block, info.sender.clone())
+        };
+        let mut added = vec![];
+        for addr in admins {
+            let addr = validate_admin_addr(deps.as_ref(), &addr)?;
+            if !ADMINS.has(deps.storage, &addr) {
+                ensure_eligible(deps.as_ref(), &addr)?;
+            }
+            if add_admin(deps.storage, deps.api, &addr, &admin_info)? { // re-adding an admin keeps their original join time
+                added.push(addr);
+            }
+        }
+
+        let cw4 = cw4_update_msg(
+            deps.as_ref(),
+            vec![],
+            added
+                .iter()
+                .map(|addr| (addr.clone(), admin_info.weight))
+                .collect(),
+        )?;
+        let welcome = welcome_msgs(deps.as_ref(), &added)?;
+        let log = log_msg(deps.as_ref(), "add_members", &info.sender, added)?;
+        Ok(Response::new()
+            .add_messages(cw4)
+            .add_messages(welcome)
+            .add_messages(log))
+    }
+    pub fn remove_members(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        admins: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        // authorize already checked the sender may remove every one of `admins`
+        let mut result = RemoveResult {
+            removed: vec![],
+            skipped: vec![],
+        };
+        for addr in admins {
+            let addr = deps.api.addr_validate(&addr)?;
+            if remove_admin_fully(deps.storage, deps.api, &addr)? {
+                result.removed.push(addr);
+            } else {
+                result.skipped.push(addr);
+            }
+        }
+
+        let fallback = activate_fallback(deps.storage, deps.api, &env)?;
+
+        let cw4 = cw4_update_msg(
+            deps.as_ref(),
+            result.removed.clone(),
+            fallback.iter().cloned().collect(),
+        )?;
+        let welcome = welcome_msgs(deps.as_ref(), fallback.iter().map(|(addr, _)| addr))?;
+        let log = log_msg(
+            deps.as_ref(),
+            "remove_members",
+            &info.sender,
+            result.removed.clone(),
+        )?;
+        let mut resp = Response::new()
+            .add_messages(cw4)
+            .add_messages(welcome)
+            .add_messages(log)
+            .set_data(to_binary(&result)?);
+        if let Some((fallback_admin, _)) = fallback {
+            resp = resp.add_attribute("fallback_activated", fallback_admin);
+        }
+        Ok(resp)
+    }
+    pub fn leave(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> { // creating our function that allows admin to leave
+        if !ADMINS.has(deps.storage, &info.sender) { // nothing to remove, don't touch storage at all
+            return Ok(Response::new());
+        }
+        // authorize checked the owner isn't required to stay and the sender's tenure is met
+
+        remove_admin_fully(deps.storage, deps.api, &info.sender)?; // only the sender's own entries are touched
+        let fallback = activate_fallback(deps.storage, deps.api, &env)?;
+
+        let cw4 = cw4_update_msg(
+            deps.as_ref(),
+            vec![info.sender.clone()],
+            fallback.iter().cloned().collect(),
+        )?;
+        let welcome = welcome_msgs(deps.as_ref(), fallback.iter().map(|(addr, _)| addr))?;
+        let log = log_msg(
+            deps.as_ref(),
+            "leave",
+            &info.sender,
+            vec![info.sender.clone()],
+        )?;
+        let mut resp = Response::new()
+            .add_messages(cw4)
+            .add_messages(welcome)
+            .add_messages(log);
+        if let Some((fallback_admin, _)) = fallback {
+            resp = resp.add_attribute("fallback_activated", fallback_admin);
+        }
+        Ok(resp)
+    }
+    pub fn set_greeting(
+        deps: DepsMut,
+        info: MessageInfo,
+        greeting: String,
+    ) -> Result<Response, ContractError> {
+        GREETING.save(deps.storage, &greeting)?;
+
+        let log = log_msg(deps.as_ref(), "set_greeting", &info.sender, vec![])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn sweep(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        denom: String,
+        to: String,
+    ) -> Result<Response, ContractError> {
+        validate_denom(&denom)?;
+
+        let to = deps.api.addr_validate(&to)?;
+        let balance = deps.querier.query_balance(&env.contract.address, denom)?;
+        if balance.amount.is_zero() {
+            return Err(ContractError::NothingToSweep {});
+        }
+
+        let send = BankMsg::Send {
+            to_address: to.to_string(),
+            amount: vec![balance],
+        };
+        let log = log_msg(deps.as_ref(), "sweep", &info.sender, vec![to])?;
+        Ok(Response::new().add_message(send).add_messages(log))
+    }
+    pub fn create_invite(
+        deps: DepsMut,
+        info: MessageInfo,
+        code_hash: Binary,
+        role: Role,
+        weight: u64,
+        expires_at: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        if INVITES.has(deps.storage, code_hash.as_slice()) {
+            return Err(ContractError::InviteExists {});
+        }
+
+        INVITES.save(
+            deps.storage,
+            code_hash.as_slice(),
+            &Invite {
+                created_by: info.sender.clone(),
+                role,
+                weight,
+                expires_at,
+            },
+        )?;
+
+        let log = log_msg(deps.as_ref(), "create_invite", &info.sender, vec![])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn redeem_invite(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        code: String,
+    ) -> Result<Response, ContractError> {
+        let code_hash = Sha256::digest(code.as_bytes());
+        let invite = INVITES
+            .may_load(deps.storage, code_hash.as_slice())?
+            .ok_or(ContractError::InviteNotFound {})?;
+        if invite
+            .expires_at
+            .is_some_and(|expires_at| is_expired(expires_at, &env))
+        {
+            return Err(ContractError::InviteExpired {});
+        }
+
+        let admin_info = AdminInfo {
+            role: invite.role,
+            weight: invite.weight,
+            ..AdminInfo::new(&env.block, invite.created_by)
+        };
+        if ADMINS.has(deps.storage, &info.sender) {
+            return Err(ContractError::AlreadyAdmin { addr: info.sender });
+        }
+        ensure_eligible(deps.as_ref(), &info.sender)?;
+        add_admin(deps.storage, deps.api, &info.sender, &admin_info)?;
+        INVITES.remove(deps.storage, code_hash.as_slice()); // invites are single use
+
+        let cw4 = cw4_update_msg(
+            deps.as_ref(),
+            vec![],
+            vec![(info.sender.clone(), admin_info.weight)],
+        )?;
+        let welcome = welcome_msgs(deps.as_ref(), [&info.sender])?;
+        let log = log_msg(
+            deps.as_ref(),
+            "redeem_invite",
+            &info.sender,
+            vec![info.sender.clone()],
+        )?;
+        Ok(Response::new()
+            .add_messages(cw4)
+            .add_messages(welcome)
+            .add_messages(log))
+    }
+    pub fn set_annotation(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+        key: String,
+        value: String,
+        expected_version: u64,
+    ) -> Result<Response, ContractError> {
+        let addr = deps.api.addr_validate(&addr)?;
+        bump_version(deps.storage, &addr, expected_version)?;
+
+        ANNOTATIONS.save(deps.storage, (&addr, key.as_str()), &value)?;
+
+        let log = log_msg(deps.as_ref(), "set_annotation", &info.sender, vec![addr])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn clear_annotations(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+        expected_version: u64,
+    ) -> Result<Response, ContractError> {
+        let addr = deps.api.addr_validate(&addr)?;
+        bump_version(deps.storage, &addr, expected_version)?;
+
+        let keys = ANNOTATIONS
+            .prefix(&addr)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for key in keys {
+            ANNOTATIONS.remove(deps.storage, (&addr, key.as_str()));
+        }
+
+        let log = log_msg(deps.as_ref(), "clear_annotations", &info.sender, vec![addr])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn update_config(
+        deps: DepsMut,
+        info: MessageInfo,
+        default_role: Option<Role>,
+    ) -> Result<Response, ContractError> {
+        let mut config = CONFIG.load(deps.storage)?;
+        if let Some(default_role) = default_role {
+            config.default_role = default_role;
+        }
+        CONFIG.save(deps.storage, &config)?;
+
+        let log = log_msg(deps.as_ref(), "update_config", &info.sender, vec![])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn transfer_ownership(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        new_owner: String,
+    ) -> Result<Response, ContractError> {
+        let new_owner = deps.api.addr_validate(&new_owner)?;
+        PENDING_OWNER.save(
+            deps.storage,
+            &PendingOwner {
+                addr: new_owner.clone(),
+                proposed_at: env.block.time,
+            },
+        )?;
+
+        let log = log_msg(
+            deps.as_ref(),
+            "transfer_ownership",
+            &info.sender,
+            vec![new_owner],
+        )?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn accept_ownership(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        // authorize checked the sender is the pending owner and the transfer delay has passed
+        let previous_owner = OWNER.load(deps.storage)?;
+        OWNER.save(deps.storage, &info.sender)?;
+        PENDING_OWNER.remove(deps.storage);
+
+        let mut added = vec![];
+        if CONFIG.load(deps.storage)?.owner_must_be_admin {
+            let admin_info = AdminInfo::new(&env.block, previous_owner);
+            if add_admin(deps.storage, deps.api, &info.sender, &admin_info)? {
+                added.push((info.sender.clone(), admin_info.weight));
+            }
+        }
+
+        let welcome = welcome_msgs(deps.as_ref(), added.iter().map(|(addr, _)| addr))?;
+        let cw4 = cw4_update_msg(deps.as_ref(), vec![], added)?;
+        let log = log_msg(
+            deps.as_ref(),
+            "accept_ownership",
+            &info.sender,
+            vec![info.sender.clone()],
+        )?;
+        Ok(Response::new()
+            .add_messages(cw4)
+            .add_messages(welcome)
+            .add_messages(log))
+    }
+    pub fn extend_expiry(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+        new_expires_at: u64,
+    ) -> Result<Response, ContractError> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let mut admin_info = ADMINS
+            .may_load(deps.storage, &addr)?
+            .ok_or_else(|| ContractError::NotAdmin { addr: addr.clone() })?;
+
+        // 0 means "never expires", so it sorts after every real height
+        let effective = |expires_at: u64| if expires_at == 0 { u64::MAX } else { expires_at };
+        if effective(new_expires_at) < effective(admin_info.expires_at) {
+            return Err(ContractError::InvalidExpiry {});
+        }
+
+        admin_info.expires_at = new_expires_at;
+        admin_info.version += 1; // no expected version, an expiry only ever moves later
+        ADMINS.save(deps.storage, &addr, &admin_info)?;
+
+        let log = log_msg(deps.as_ref(), "extend_expiry", &info.sender, vec![addr])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn set_maintenance(
+        deps: DepsMut,
+        info: MessageInfo,
+        until: Option<Timestamp>,
+    ) -> Result<Response, ContractError> {
+        CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+            config.maintenance_until = until;
+            Ok(config)
+        })?;
+
+        let log = log_msg(deps.as_ref(), "set_maintenance", &info.sender, vec![])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn merge_from(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        other: String,
+    ) -> Result<Response, ContractError> {
+        let other = deps.api.addr_validate(&other)?;
+        if other == env.contract.address {
+            return Err(ContractError::InvalidAddress {
+                addr: other.into_string(),
+            });
+        }
+        let admins = other_admins(deps.as_ref(), &other)?;
+
+        // same checks as add_members, admins already here are skipped
+        let admin_info = AdminInfo {
+            role: CONFIG.load(deps.storage)?.default_role,
+            ..AdminInfo::new(&env.block, info.sender.clone())
+        };
+        let mut added = vec![];
+        for addr in admins {
+            if ADMINS.has(deps.storage, &addr) {
+                continue;
+            }
+            ensure_eligible(deps.as_ref(), &addr)?;
+            add_admin(deps.storage, deps.api, &addr, &admin_info)?;
+            added.push(addr);
+        }
+
+        let cw4 = cw4_update_msg(
+            deps.as_ref(),
+            vec![],
+            added
+                .iter()
+                .map(|addr| (addr.clone(), admin_info.weight))
+                .collect(),
+        )?;
+        let welcome = welcome_msgs(deps.as_ref(), &added)?;
+        let log = log_msg(deps.as_ref(), "merge_from", &info.sender, added)?;
+        Ok(Response::new()
+            .add_messages(cw4)
+            .add_messages(welcome)
+            .add_messages(log))
+    }
+    pub fn export_roster(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        to: String,
+        freeze: bool,
+    ) -> Result<Response, ContractError> {
+        let to = deps.api.addr_validate(&to)?;
+        if to == env.contract.address {
+            return Err(ContractError::InvalidAddress {
+                addr: to.into_string(),
+            });
+        }
+
+        let admins = admins_ordered(deps.storage)?
+            .into_iter()
+            .map(|(addr, admin_info)| admin_record(deps.storage, addr, admin_info))
+            .collect::<StdResult<_>>()?;
+        let export = WasmMsg::Execute {
+            contract_addr: to.to_string(),
+            msg: to_binary(&ExecuteMsg::ImportRoster { admins })?,
+            funds: vec![],
+        };
+        if freeze {
+            FROZEN.save(deps.storage, &true)?;
+        }
+
+        let log = log_msg(deps.as_ref(), "export_roster", &info.sender, vec![to])?;
+        Ok(Response::new().add_message(export).add_messages(log))
+    }
+    pub fn import_roster(
+        deps: DepsMut,
+        info: MessageInfo,
+        admins: Vec<AdminRecord>,
+    ) -> Result<Response, ContractError> {
+        if ADMIN_COUNT.load(deps.storage)? > 0 { // importing over live admins would merge two rosters
+            return Err(ContractError::AlreadyPopulated {});
+        }
+
+        let mut added = vec![];
+        for record in admins {
+            let addr = validate_admin_addr(deps.as_ref(), &record.addr)?;
+            ensure_eligible(deps.as_ref(), &addr)?;
+            let admin_info = AdminInfo { // restored verbatim, including the original join time
+                joined: record.joined,
+                joined_height: record.joined_height,
+                role: record.role,
+                weight: record.weight,
+                expires_at: record.expires_at,
+                added_by: deps.api.addr_validate(&record.added_by)?,
+                rank: record.rank,
+                version: record.version,
+            };
+            if let Some(label) = &record.label {
+                validate_label(deps.as_ref(), label)?;
+                LABELS.save(deps.storage, &addr, label)?;
+            }
+            for (key, value) in &record.annotations {
+                ANNOTATIONS.save(deps.storage, (&addr, key.as_str()), value)?;
+            }
+            if add_admin(deps.storage, deps.api, &addr, &admin_info)? {
+                added.push((addr, admin_info.weight));
+            }
+        }
+
+        let affected: Vec<_> = added.iter().map(|(addr, _)| addr.clone()).collect();
+        let welcome = welcome_msgs(deps.as_ref(), &affected)?;
+        let cw4 = cw4_update_msg(deps.as_ref(), vec![], added)?;
+        let log = log_msg(deps.as_ref(), "import_roster", &info.sender, affected)?;
+        Ok(Response::new()
+            .add_messages(cw4)
+            .add_messages(welcome)
+            .add_messages(log))
+    }
+    pub fn donate_to(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipients: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        validate_donation(deps.as_ref(), &info.funds)?;
+
+        let mut admins = vec![];
+        for addr in recipients {
+            let addr = deps.api.addr_validate(&addr)?;
+            match ADMINS.may_load(deps.storage, &addr)? {
+                Some(admin_info) if is_active(&admin_info, &env) => {}
+                _ => return Err(ContractError::NotAdmin { addr }),
+            }
+            if !admins.contains(&addr) { // naming a recipient twice doesn't give them two shares
+                admins.push(addr);
+            }
+        }
+        if admins.is_empty() {
+            return Err(ContractError::NoRecipients {});
+        }
+
+        let sends = split_funds(&info.funds, &admins);
+        record_donation(deps.storage, &info.funds)?;
+        let log = log_msg(deps.as_ref(), "donate_to", &info.sender, admins)?;
+        Ok(Response::new().add_messages(sends).add_messages(log))
+    }
+    pub fn set_label(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+        label: String,
+        expected_version: u64,
+    ) -> Result<Response, ContractError> {
+        let addr = deps.api.addr_validate(&addr)?;
+        if !ADMINS.has(deps.storage, &addr) {
+            return Err(ContractError::NotAdmin { addr });
+        }
+        validate_label(deps.as_ref(), &label)?;
+        bump_version(deps.storage, &addr, expected_version)?;
+        LABELS.save(deps.storage, &addr, &label)?;
+
+        let log = log_msg(deps.as_ref(), "set_label", &info.sender, vec![addr])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn set_rank(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+        rank: u32,
+        expected_version: u64,
+    ) -> Result<Response, ContractError> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let mut admin_info = bump_version(deps.storage, &addr, expected_version)?;
+        admin_info.rank = rank;
+        ADMINS.save(deps.storage, &addr, &admin_info)?;
+
+        let log = log_msg(deps.as_ref(), "set_rank", &info.sender, vec![addr])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn set_role_capabilities(
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+        capabilities: Capabilities,
+    ) -> Result<Response, ContractError> {
+        ROLE_CAPABILITIES.save(deps.storage, role.key(), &capabilities)?;
+
+        let log = log_msg(deps.as_ref(), "set_role_capabilities", &info.sender, vec![])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn propose_freeze(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let freeze_at = env
+            .block
+            .time
+            .plus_seconds(CONFIG.load(deps.storage)?.freeze_delay);
+        FREEZE_AT.save(deps.storage, &freeze_at)?; // proposing again restarts the delay
+
+        let log = log_msg(deps.as_ref(), "propose_freeze", &info.sender, vec![])?;
+        Ok(Response::new()
+            .add_messages(log)
+            .add_attribute("freeze_at", freeze_at.to_string()))
+    }
+    pub fn cancel_freeze(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        // once the freeze took effect authorize rejects this like every other exec
+        if !FREEZE_AT.exists(deps.storage) {
+            return Err(ContractError::NoPendingFreeze {});
+        }
+        FREEZE_AT.remove(deps.storage);
+
+        let log = log_msg(deps.as_ref(), "cancel_freeze", &info.sender, vec![])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn set_labels(
+        deps: DepsMut,
+        info: MessageInfo,
+        entries: Vec<(String, String)>,
+    ) -> Result<Response, ContractError> {
+        let mut labeled = vec![];
+        for (addr, label) in entries {
+            let addr = deps.api.addr_validate(&addr)?;
+            let mut admin_info = match ADMINS.may_load(deps.storage, &addr)? {
+                Some(admin_info) => admin_info,
+                None => continue,
+            };
+            validate_label(deps.as_ref(), &label)?;
+            admin_info.version += 1; // no expected version for bulk setup, but the label still changed
+            ADMINS.save(deps.storage, &addr, &admin_info)?;
+            LABELS.save(deps.storage, &addr, &label)?;
+            labeled.push(addr);
+        }
+
+        let log = log_msg(deps.as_ref(), "set_labels", &info.sender, labeled)?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn reorder(
+        deps: DepsMut,
+        info: MessageInfo,
+        order: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        // admins left out of `order` keep their rank
+        let mut reordered = vec![];
+        for (rank, addr) in order.into_iter().enumerate() {
+            let addr = deps.api.addr_validate(&addr)?;
+            if reordered.contains(&addr) { // an admin can only hold one position
+                return Err(ContractError::DuplicateAddress { addr });
+            }
+            let mut admin_info = ADMINS
+                .may_load(deps.storage, &addr)?
+                .ok_or_else(|| ContractError::NotAdmin { addr: addr.clone() })?;
+            admin_info.rank = rank as u32;
+            admin_info.version += 1; // no expected version for a bulk reorder, but the rank still changed
+            ADMINS.save(deps.storage, &addr, &admin_info)?;
+            reordered.push(addr);
+        }
+
+        let log = log_msg(deps.as_ref(), "reorder", &info.sender, reordered)?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn prune_proposals(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let additions = expired_proposals(deps.storage, &env, &PROPOSED_AT)?;
+        for addr in &additions {
+            PROPOSED_ADMINS.remove(deps.storage, addr);
+            PROPOSED_AT.remove(deps.storage, addr);
+        }
+        let removals = expired_proposals(deps.storage, &env, &REMOVAL_PROPOSED_AT)?;
+        for addr in &removals {
+            PENDING_REMOVALS.remove(deps.storage, addr);
+            REMOVAL_PROPOSED_AT.remove(deps.storage, addr);
+        }
+
+        let pruned = additions.len() + removals.len();
+        let log = log_msg(
+            deps.as_ref(),
+            "prune_proposals",
+            &info.sender,
+            additions.into_iter().chain(removals).collect(),
+        )?;
+        Ok(Response::new()
+            .add_messages(log)
+            .add_attribute("pruned", pruned.to_string()))
+    }
+    pub fn poke_expiry(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        // same rules as pruning in migrate, expired admins only stop counting as active otherwise
+        let expired = remove_expired(deps.branch(), &env)?;
+        if expired.is_empty() {
+            return Err(ContractError::NothingExpired {});
+        }
+        let fallback = activate_fallback(deps.storage, deps.api, &env)?;
+
+        let events = expired
+            .iter()
+            .map(|addr| Event::new("admin_expired").add_attribute("addr", addr))
+            .collect::<Vec<_>>();
+        let cw4 = cw4_update_msg(
+            deps.as_ref(),
+            expired.clone(),
+            fallback.iter().cloned().collect(),
+        )?;
+        let welcome = welcome_msgs(deps.as_ref(), fallback.iter().map(|(addr, _)| addr))?;
+        let log = log_msg(deps.as_ref(), "poke_expiry", &info.sender, expired)?;
+        let mut resp = Response::new()
+            .add_messages(cw4)
+            .add_messages(welcome)
+            .add_messages(log)
+            .add_events(events);
+        if let Some((fallback_admin, _)) = fallback {
+            resp = resp.add_attribute("fallback_activated", fallback_admin);
+        }
+        Ok(resp)
+    }
+    pub fn set_rate_guard(
+        deps: DepsMut,
+        info: MessageInfo,
+        max_changes: u64,
+        window: u64,
+    ) -> Result<Response, ContractError> {
+        let rate_guard = (window > 0).then_some(RateGuard {
+            max_changes,
+            window,
+        });
+        CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+            config.rate_guard = rate_guard;
+            Ok(config)
+        })?;
+        RECENT_CHANGES.remove(deps.storage); // a new window starts counting from scratch
+
+        let log = log_msg(deps.as_ref(), "set_rate_guard", &info.sender, vec![])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn delegate(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        to: String,
+        until: Timestamp,
+    ) -> Result<Response, ContractError> {
+        let to = deps.api.addr_validate(&to)?;
+        if to == info.sender {
+            return Err(ContractError::CannotDelegateToSelf {});
+        }
+        if let Some((_, current_until)) = DELEGATIONS.may_load(deps.storage, &info.sender)? {
+            if env.block.time < current_until { // one delegation per admin, an expired one is replaced
+                return Err(ContractError::DelegationExists {});
+            }
+        }
+        DELEGATIONS.save(deps.storage, &info.sender, &(to.clone(), until))?;
+
+        let log = log_msg(deps.as_ref(), "delegate", &info.sender, vec![to])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn revoke_delegation(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        let (delegate, _) = DELEGATIONS
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::NoDelegation {})?;
+        DELEGATIONS.remove(deps.storage, &info.sender);
+
+        let log = log_msg(
+            deps.as_ref(),
+            "revoke_delegation",
+            &info.sender,
+            vec![delegate],
+        )?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn set_my_label(
+        deps: DepsMut,
+        info: MessageInfo,
+        label: String,
+        expected_version: u64,
+    ) -> Result<Response, ContractError> {
+        validate_label(deps.as_ref(), &label)?;
+        bump_version(deps.storage, &info.sender, expected_version)?;
+        LABELS.save(deps.storage, &info.sender, &label)?;
+
+        let log = log_msg(
+            deps.as_ref(),
+            "set_label",
+            &info.sender,
+            vec![info.sender.clone()],
+        )?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn owner_remove(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+    ) -> Result<Response, ContractError> {
+        let addr = deps.api.addr_validate(&addr)?;
+        if addr == info.sender {
+            return Err(ContractError::CannotRemoveSelf {});
+        }
+        if !ADMINS.has(deps.storage, &addr) {
+            return Err(ContractError::NotAdmin { addr });
+        }
+        if ADMIN_COUNT.load(deps.storage)? <= 1 {
+            return Err(ContractError::LastAdmin {});
+        }
+        remove_admin_fully(deps.storage, deps.api, &addr)?;
+
+        let cw4 = cw4_update_msg(deps.as_ref(), vec![addr.clone()], vec![])?;
+        let log = log_msg(deps.as_ref(), "owner_remove", &info.sender, vec![addr])?;
+        Ok(Response::new().add_messages(cw4).add_messages(log))
+    }
+    pub fn prune_inactive(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        duration: u64,
+    ) -> Result<Response, ContractError> {
+        let mut removed = vec![];
+        for addr in inactive_admins(deps.storage, &env, duration)? {
+            // the owner and the last remaining admin are always spared
+            if addr == info.sender || ADMIN_COUNT.load(deps.storage)? <= 1 {
+                continue;
+            }
+            remove_admin_fully(deps.storage, deps.api, &addr)?;
+            removed.push(addr);
+        }
+
+        let pruned = removed.len();
+        let cw4 = cw4_update_msg(deps.as_ref(), removed.clone(), vec![])?;
+        let log = log_msg(deps.as_ref(), "prune_inactive", &info.sender, removed)?;
+        Ok(Response::new()
+            .add_messages(cw4)
+            .add_messages(log)
+            .add_attribute("pruned", pruned.to_string()))
+    }
+    pub fn propose_remove(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        addr: String,
+    ) -> Result<Response, ContractError> {
+        let addr = deps.api.addr_validate(&addr)?;
+        if PENDING_REMOVALS.has(deps.storage, &addr)
+            && !is_proposal_expired(
+                &CONFIG.load(deps.storage)?,
+                &env,
+                REMOVAL_PROPOSED_AT.may_load(deps.storage, &addr)?,
+            )
+        {
+            return Err(ContractError::RemovalPending { addr });
+        }
+        // an expired proposal is replaced, starting over without its approvals
+        REMOVAL_PROPOSED_AT.save(deps.storage, &addr, &env.block.time)?;
+        approve_removal(deps, env, info, addr, vec![])
+    }
+    pub fn approve_remove(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        addr: String,
+    ) -> Result<Response, ContractError> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let approvals = PENDING_REMOVALS
+            .may_load(deps.storage, &addr)?
+            .ok_or_else(|| ContractError::NoPendingRemoval { addr: addr.clone() })?;
+        let proposed_at = REMOVAL_PROPOSED_AT.may_load(deps.storage, &addr)?;
+        if is_proposal_expired(&CONFIG.load(deps.storage)?, &env, proposed_at) {
+            return Err(ContractError::ProposalExpired { addr });
+        }
+        approve_removal(deps, env, info, addr, approvals)
+    }
+
+    // Adds the sender to `approvals` and removes `addr` once removal_threshold approvals are in,
+    // without a threshold the first approval is enough. Same rules as RemoveMembers otherwise.
+    // Approvals from admins that have left or been removed since don't count.
+    fn approve_removal(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        addr: Addr,
+        mut approvals: Vec<Addr>,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if addr == owner && info.sender != addr {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+        if !ADMINS.has(deps.storage, &addr) {
+            return Err(ContractError::NotAdmin { addr });
+        }
+        ensure_not_owner_removal(deps.as_ref(), &addr)?;
+        approvals.retain(|approver| *approver == owner || ADMINS.has(deps.storage, approver));
+        // a delegate approves as the admin delegating to them, so the two only count once
+        let approver = if info.sender == owner
+            || has_capability(deps.as_ref(), &env, &info.sender, |caps| caps.can_remove)?
+        {
+            info.sender.clone()
+        } else {
+            delegating_admin(deps.as_ref(), &env, &info.sender, |caps| caps.can_remove)?
+                .ok_or_else(|| ContractError::Unauthorized {
+                    sender: info.sender.clone(),
+                })?
+        };
+        if approvals.contains(&approver) {
+            return Err(ContractError::AlreadyApproved { sender: approver });
+        }
+        approvals.push(approver);
+
+        let threshold = CONFIG.load(deps.storage)?.removal_threshold.unwrap_or(1);
+        if approvals.len() < threshold as usize {
+            PENDING_REMOVALS.save(deps.storage, &addr, &approvals)?;
+            let log = log_msg(deps.as_ref(), "approve_remove", &info.sender, vec![addr])?;
+            return Ok(Response::new()
+                .add_messages(log)
+                .add_attribute("approvals", approvals.len().to_string()));
+        }
+
+        remove_admin_fully(deps.storage, deps.api, &addr)?;
+        let fallback = activate_fallback(deps.storage, deps.api, &env)?;
+
+        let cw4 = cw4_update_msg(
+            deps.as_ref(),
+            vec![addr.clone()],
+            fallback.iter().cloned().collect(),
+        )?;
+        let welcome = welcome_msgs(deps.as_ref(), fallback.iter().map(|(addr, _)| addr))?;
+        let log = log_msg(deps.as_ref(), "approve_remove", &info.sender, vec![addr])?;
+        let mut resp = Response::new()
+            .add_messages(cw4)
+            .add_messages(welcome)
+            .add_messages(log)
+            .add_attribute("approvals", approvals.len().to_string());
+        if let Some((fallback_admin, _)) = fallback {
+            resp = resp.add_attribute("fallback_activated", fallback_admin);
+        }
+        Ok(resp)
+    }
+    pub fn swap_roles(
+        deps: DepsMut,
+        info: MessageInfo,
+        a: String,
+        b: String,
+    ) -> Result<Response, ContractError> {
+        let a = deps.api.addr_validate(&a)?;
+        let b = deps.api.addr_validate(&b)?;
+        let mut a_info = ADMINS
+            .may_load(deps.storage, &a)?
+            .ok_or_else(|| ContractError::NotAdmin { addr: a.clone() })?;
+        let mut b_info = ADMINS
+            .may_load(deps.storage, &b)?
+            .ok_or_else(|| ContractError::NotAdmin { addr: b.clone() })?;
+
+        std::mem::swap(&mut a_info.role, &mut b_info.role);
+        a_info.version += 1; // no expected version for a swap, but both roles still changed
+        b_info.version += 1;
+        ADMINS.save(deps.storage, &a, &a_info)?;
+        ADMINS.save(deps.storage, &b, &b_info)?;
+
+        let log = log_msg(deps.as_ref(), "swap_roles", &info.sender, vec![a, b])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn propose_admin(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        addr: String,
+    ) -> Result<Response, ContractError> {
+        let addr = validate_admin_addr(deps.as_ref(), &addr)?;
+        if ADMINS.has(deps.storage, &addr) {
+            return Err(ContractError::AlreadyAdmin { addr });
+        }
+        PROPOSED_ADMINS.save(deps.storage, &addr, &info.sender)?;
+        PROPOSED_AT.save(deps.storage, &addr, &env.block.time)?;
+
+        let log = log_msg(deps.as_ref(), "propose_admin", &info.sender, vec![addr])?;
+        Ok(Response::new().add_messages(log))
+    }
+    pub fn accept_admin(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        // authorize checked the proposal is still open and its proposer can still add admins
+        let proposed_by = PROPOSED_ADMINS.load(deps.storage, &info.sender)?;
+        if ADMINS.has(deps.storage, &info.sender) {
+            return Err(ContractError::AlreadyAdmin { addr: info.sender });
+        }
+        ensure_eligible(deps.as_ref(), &info.sender)?;
+        PROPOSED_ADMINS.remove(deps.storage, &info.sender);
+        PROPOSED_AT.remove(deps.storage, &info.sender);
+
+        let admin_info = AdminInfo {
+            role: config.default_role,
+            ..AdminInfo::new(&env.block, proposed_by)
+        };
+        add_admin(deps.storage, deps.api, &info.sender, &admin_info)?;
+
+        let cw4 = cw4_update_msg(
+            deps.as_ref(),
+            vec![],
+            vec![(info.sender.clone(), admin_info.weight)],
+        )?;
+        let welcome = welcome_msgs(deps.as_ref(), [&info.sender])?;
+        let log = log_msg(
+            deps.as_ref(),
+            "accept_admin",
+            &info.sender,
+            vec![info.sender.clone()],
+        )?;
+        Ok(Response::new()
+            .add_messages(cw4)
+            .add_messages(welcome)
+            .add_messages(log))
+    }
+}
+mod query {
+    use super::*;
+
+    // Queries that don't take `env` (Greet, AdminsList and most others) depend on stored state
+    // only, so clients can cache their results until the next exec. Passing `env` to one of them
+    // takes that guarantee away, keep the time-based ones separate.
+
+    // query errors use a stable "admins::" prefix so clients can match on them
+    fn validate_addr(deps: Deps, addr: &str) -> StdResult<Addr> {
+        deps.api
+            .addr_validate(addr)
+            .map_err(|err| StdError::generic_err(format!("admins::invalid_address: {addr}: {err}")))
+    }
+
+    const MAX_LIMIT: u32 = 100;
+
+    // limits above MAX_LIMIT are clamped, or rejected when the contract uses strict limits
+    fn validate_limit(deps: Deps, limit: u32) -> StdResult<usize> {
+        if limit == 0 {
+            return Err(StdError::generic_err(
+                "admins::invalid_limit: limit must be greater than zero",
+            ));
+        }
+        if limit > MAX_LIMIT {
+            if CONFIG.load(deps.storage)?.strict_limits {
+                return Err(StdError::generic_err(format!(
+                    "admins::limit_too_large: limit must not exceed {MAX_LIMIT}"
+                )));
+            }
+            return Ok(MAX_LIMIT as usize);
+        }
+        Ok(limit as usize)
+    }
+
+    pub fn greet(deps: Deps) -> StdResult<GreetResp> { // function for our greet query 
+        let resp = GreetResp {
+            message: GREETING.load(deps.storage)?,
+        };
+
+        Ok(resp)
+    }
+    pub fn admins_list( // admins come back in the canonical order, see state::admins_ordered
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AdminsListResp> {
+        let limit = validate_limit(deps, limit.unwrap_or(MAX_LIMIT))?;
+        let start_after = start_after
+            .map(|addr| validate_addr(deps, &addr))
+            .transpose()?;
+        if let Some(addr) = &start_after {
+            // the cursor is located by its join time, which is gone once the admin leaves
+            if !ADMINS.has(deps.storage, addr) {
+                return Err(StdError::generic_err(format!(
+                    "admins::stale_cursor: {addr} is no longer an admin, page again from the start"
+                )));
+            }
+        }
+        let admins = admins_ordered_page(deps.storage, deps.api, start_after.as_ref(), limit)?
+            .into_iter()
+            .map(|(addr, _)| addr)
+            .collect();
+        let resp = AdminsListResp { admins };
+        Ok(resp)
+    }
+    pub fn reconcile(deps: Deps, expected: Vec<String>) -> StdResult<ReconcileResp> {
+        let mut expected = expected
+            .into_iter()
+            .map(|addr| validate_addr(deps, &addr))
+            .collect::<StdResult<Vec<_>>>()?;
+        expected.sort();
+        expected.dedup();
+
+        let missing = expected
+            .iter()
+            .filter(|addr| !ADMINS.has(deps.storage, addr))
+            .cloned()
+            .collect();
+        let extra = admins_ordered(deps.storage)?
+            .into_iter()
+            .map(|(addr, _)| addr)
+            .filter(|addr| expected.binary_search(addr).is_err())
+            .collect();
+
+        Ok(ReconcileResp { missing, extra })
+    }
+    pub fn diff_from(deps: Deps, other: String) -> StdResult<DiffFromResp> {
+        // the other side of exec::merge_from, reading the other roster the same way
+        let other = validate_addr(deps, &other)?;
+        let other_admins = other_admins(deps, &other)?;
+
+        let admins = admins_ordered(deps.storage)?
+            .into_iter()
+            .map(|(addr, _)| addr)
+            .filter(|addr| !other_admins.contains(addr))
+            .collect();
+        Ok(DiffFromResp { admins })
+    }
+    pub fn intersection(deps: Deps, addrs: Vec<String>) -> StdResult<IntersectionResp> {
+        if addrs.len() > MAX_LIMIT as usize {
+            return Err(StdError::generic_err(format!(
+                "admins::too_many_addrs: at most {MAX_LIMIT} addresses can be intersected"
+            )));
+        }
+        let mut addrs = addrs
+            .into_iter()
+            .map(|addr| validate_addr(deps, &addr))
+            .collect::<StdResult<Vec<_>>>()?;
+        addrs.sort();
+        addrs.dedup();
+
+        let admins = addrs
+            .into_iter()
+            .filter(|addr| ADMINS.has(deps.storage, addr))
+            .collect();
+        Ok(IntersectionResp { admins })
+    }
+    pub fn admins_preview(deps: Deps, limit: u32) -> StdResult<AdminsPreviewResp> { // sample for "3 of 142 admins" style headers
+        let limit = validate_limit(deps, limit)?;
+        let sample = admins_ordered_page(deps.storage, deps.api, None, limit)?
+            .into_iter()
+            .map(|(addr, _)| addr)
+            .collect();
+        let total = ADMIN_COUNT.load(deps.storage)?;
+
+        Ok(AdminsPreviewResp { sample, total })
+    }
+    pub fn invites(
+        deps: Deps,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    ) -> StdResult<InvitesResp> {
+        let limit = validate_limit(deps, limit.unwrap_or(MAX_LIMIT))?;
+        let start = start_after
+            .as_ref()
+            .map(|hash| Bound::exclusive(hash.as_slice()));
+        let invites = INVITES
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (code_hash, invite) = item?;
+                Ok(InviteEntry {
+                    code_hash: code_hash.into(),
+                    role: invite.role,
+                    weight: invite.weight,
+                    expires_at: invite.expires_at,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(InvitesResp { invites })
+    }
+    pub fn annotations(deps: Deps, addr: String) -> StdResult<AnnotationsResp> {
+        let addr = validate_addr(deps, &addr)?;
+        let annotations = ANNOTATIONS
+            .prefix(&addr)
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+
+        Ok(AnnotationsResp { annotations })
+    }
+    pub fn admins_by_role(deps: Deps) -> StdResult<AdminsByRoleResp> {
+        let mut roles: BTreeMap<Role, Vec<Addr>> = BTreeMap::new();
+        for (addr, admin_info) in admins_ordered(deps.storage)? {
+            roles.entry(admin_info.role).or_default().push(addr);
+        }
+
+        Ok(AdminsByRoleResp {
+            roles: roles.into_iter().collect(),
+        })
+    }
+    pub fn admins_by_min_weight(
+        deps: Deps,
+        min: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AdminsByMinWeightResp> {
+        let limit = validate_limit(deps, limit.unwrap_or(MAX_LIMIT))?;
+        let start_after = start_after
+            .map(|addr| validate_addr(deps, &addr))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+        let admins = ADMINS
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter(|item| !matches!(item, Ok((_, admin_info)) if admin_info.weight < min))
+            .take(limit)
+            .map(|item| item.map(|(addr, admin_info)| (addr, admin_info.weight)))
+            .collect::<StdResult<_>>()?;
+
+        Ok(AdminsByMinWeightResp { admins })
+    }
+    pub fn index_of(deps: Deps, addr: String) -> StdResult<IndexOfResp> {
+        let addr = validate_addr(deps, &addr)?;
+        let target = match ADMINS.may_load(deps.storage, &addr)? {
+            Some(target) => target,
+            None => return Ok(IndexOfResp { index: None }),
+        };
+
+        // the admins ahead of the target are the index keys below its own, counted without loading them
+        let canonical = deps.api.addr_canonicalize(addr.as_str())?;
+        let end = Bound::exclusive((target.joined.nanos(), canonical.as_slice()));
+        let index = ADMINS_BY_JOINED
+            .keys_raw(deps.storage, None, Some(end), Order::Ascending)
+            .count() as u32;
+
+        Ok(IndexOfResp { index: Some(index) })
+    }
+    pub fn state_fingerprint(deps: Deps) -> StdResult<StateFingerprintResp> {
+        // admins are hashed in storage key order, which is already sorted by address
+        let mut hasher = Sha256::new();
+        for item in ADMINS.range(deps.storage, None, None, Order::Ascending) {
+            let (addr, admin_info) = item?;
+            hasher.update(addr.as_bytes());
+            hasher.update(to_vec(&admin_info)?);
+        }
+        hasher.update(to_vec(&CONFIG.load(deps.storage)?)?);
+
+        Ok(StateFingerprintResp {
+            fingerprint: hasher.finalize().to_vec().into(),
+        })
+    }
+    pub fn pending_owner_ready_at(deps: Deps) -> StdResult<PendingOwnerResp> {
+        let pending = match PENDING_OWNER.may_load(deps.storage)? {
+            Some(pending) => pending,
+            None => {
+                return Ok(PendingOwnerResp {
+                    pending_owner: None,
+                    ready_at: None,
+                })
+            }
+        };
+        let ready_at = pending
+            .proposed_at
+            .plus_seconds(CONFIG.load(deps.storage)?.transfer_delay);
+
+        Ok(PendingOwnerResp {
+            pending_owner: Some(pending.addr),
+            ready_at: Some(ready_at),
+        })
+    }
+    pub fn expiring_soon(deps: Deps, env: Env, within_blocks: u64) -> StdResult<ExpiringSoonResp> {
+        let until = env.block.height.saturating_add(within_blocks);
+        let mut admins: Vec<_> = admins_ordered(deps.storage)?
+            .into_iter()
+            .filter(|(_, admin_info)| {
+                is_active(admin_info, &env)
+                    && admin_info.expires_at != 0
+                    && admin_info.expires_at <= until
+            })
+            .map(|(addr, admin_info)| (addr, admin_info.expires_at))
+            .collect();
+        admins.sort_by_key(|(_, expires_at)| *expires_at); // stable, so ties keep the canonical order
+
+        Ok(ExpiringSoonResp { admins })
+    }
+    pub fn next_expiry(deps: Deps, env: Env) -> StdResult<NextExpiryResp> {
+        // min_by_key keeps the first of equal heights, so ties go by the canonical order
+        let next = admins_ordered(deps.storage)?
+            .into_iter()
+            .filter(|(_, admin_info)| is_active(admin_info, &env) && admin_info.expires_at != 0)
+            .map(|(addr, admin_info)| (addr, admin_info.expires_at))
+            .min_by_key(|(_, expires_at)| *expires_at);
+        Ok(NextExpiryResp { next })
+    }
+    pub fn both_admins(deps: Deps, a: String, b: String) -> StdResult<BothAdminsResp> {
+        let a_is_admin = ADMINS.has(deps.storage, &validate_addr(deps, &a)?);
+        let b_is_admin = ADMINS.has(deps.storage, &validate_addr(deps, &b)?);
+
+        Ok(BothAdminsResp {
+            a_is_admin,
+            b_is_admin,
+            both: a_is_admin && b_is_admin,
+        })
+    }
+    pub fn created_at(deps: Deps) -> StdResult<CreatedAtResp> {
+        let (height, time) = CREATED.load(deps.storage)?;
+        Ok(CreatedAtResp { height, time })
+    }
+    pub fn availability(deps: Deps, env: Env) -> StdResult<AvailabilityResp> {
+        // the parts of exec::authorize that hold for every sender, run on each flag's message
+        let mut reasons = vec![];
+        let mut check = |flag: &str, msg: ExecuteMsg| -> StdResult<bool> {
+            let gates = exec::ensure_available(deps, &env, &msg)
+                .and_then(|()| exec::ensure_enabled(deps, &msg));
+            match gates {
+                Ok(()) => Ok(true),
+                Err(ContractError::StdError(err)) => Err(err),
+                Err(err) => {
+                    reasons.push(format!("{flag}: {err}"));
+                    Ok(false)
+                }
+            }
+        };
+        let can_add = check("can_add", ExecuteMsg::AddMembers { admins: vec![] })?;
+        let can_remove = check("can_remove", ExecuteMsg::RemoveMembers { admins: vec![] })?;
+        let can_leave = check("can_leave", ExecuteMsg::Leave {})?;
+
+        Ok(AvailabilityResp {
+            can_add,
+            can_remove,
+            can_leave,
+            reasons,
+        })
+    }
+    pub fn check_integrity(deps: Deps) -> StdResult<IntegrityResp> {
+        let mut problems = vec![];
+
+        let admins = ADMINS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        let count = ADMIN_COUNT.load(deps.storage)?;
+        if count as usize != admins.len() {
+            problems.push(format!(
+                "admin_count is {count} but {} admins are stored",
+                admins.len()
+            ));
+        }
+        // a key that doesn't validate back to itself could alias another admin's address
+        for addr in &admins {
+            match deps.api.addr_validate(addr.as_str()) {
+                Ok(validated) if validated == *addr => {}
+                _ => problems.push(format!("admin key {addr} is not a normalized address")),
+            }
+        }
+        // every admin has exactly the join-time index entry add_admin wrote, and nobody else has one
+        for entry in ADMINS_BY_JOINED.range(deps.storage, None, None, Order::Ascending) {
+            let ((joined, canonical), addr) = entry?;
+            let expected = match ADMINS.may_load(deps.storage, &addr)? {
+                Some(admin_info) => (
+                    admin_info.joined.nanos(),
+                    deps.api.addr_canonicalize(addr.as_str())?.to_vec(),
+                ),
+                None => {
+                    problems.push(format!("join-time index entry stored for non-admin {addr}"));
+                    continue;
+                }
+            };
+            if (joined, canonical) != expected {
+                problems.push(format!(
+                    "join-time index entry for {addr} doesn't match its join time"
+                ));
+            }
+        }
+        for item in ADMINS.range(deps.storage, None, None, Order::Ascending) {
+            let (addr, admin_info) = item?;
+            let canonical = deps.api.addr_canonicalize(addr.as_str())?;
+            let key = (admin_info.joined.nanos(), canonical.as_slice());
+            if !ADMINS_BY_JOINED.has(deps.storage, key) {
+                problems.push(format!("admin {addr} is missing from the join-time index"));
+            }
+        }
+        if CONFIG.load(deps.storage)?.owner_must_be_admin {
+            let owner = OWNER.load(deps.storage)?;
+            if !ADMINS.has(deps.storage, &owner) {
+                problems.push(format!(
+                    "owner {owner} is not an admin although owner_must_be_admin is set"
+                ));
+            }
+        }
+
+        let mut annotated = ANNOTATIONS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .map(|key| key.map(|(addr, _)| addr))
+            .collect::<StdResult<Vec<_>>>()?;
+        annotated.dedup();
+        for addr in annotated {
+            if !ADMINS.has(deps.storage, &addr) {
+                problems.push(format!("annotations stored for non-admin {addr}"));
+            }
+        }
+        // the per-admin entries remove_admin_fully clears along with the admin
+        let per_admin = [
+            (
+                "last action",
+                LAST_ACTION.keys(deps.storage, None, None, Order::Ascending),
+            ),
+            (
+                "label",
+                LABELS.keys(deps.storage, None, None, Order::Ascending),
+            ),
+            (
+                "delegation",
+                DELEGATIONS.keys(deps.storage, None, None, Order::Ascending),
+            ),
+            (
+                "pending removal",
+                PENDING_REMOVALS.keys(deps.storage, None, None, Order::Ascending),
+            ),
+            (
+                "removal proposal time",
+                REMOVAL_PROPOSED_AT.keys(deps.storage, None, None, Order::Ascending),
+            ),
+        ];
+        for (what, keys) in per_admin {
+            for addr in keys {
+                let addr = addr?;
+                if !ADMINS.has(deps.storage, &addr) {
+                    problems.push(format!("{what} stored for non-admin {addr}"));
+                }
+            }
+        }
+
+        Ok(IntegrityResp {
+            ok: problems.is_empty(),
+            problems,
+        })
+    }
+    pub fn pending_self_accept(deps: Deps, env: Env) -> StdResult<PendingSelfAcceptResp> {
+        let expired = expired_proposals(deps.storage, &env, &PROPOSED_AT)?;
+        let mut pending = vec![];
+        for item in PROPOSED_ADMINS.range(deps.storage, None, None, Order::Ascending) {
+            let (addr, proposed_by) = item?;
+            // AcceptAdmin rejects these, just like expired ones
+            if expired.contains(&addr)
+                || !has_delegated_capability(deps, &env, &proposed_by, |caps| caps.can_add)?
+            {
+                continue;
+            }
+            pending.push((addr, proposed_by));
+        }
+        Ok(PendingSelfAcceptResp { pending })
+    }
+    pub fn added_by(deps: Deps, addr: String) -> StdResult<AddedByResp> {
+        let addr = validate_addr(deps, &addr)?;
+        let added_by = ADMINS
+            .may_load(deps.storage, &addr)?
+            .map(|admin_info| admin_info.added_by);
+        Ok(AddedByResp { added_by })
+    }
+    pub fn removable_by(deps: Deps, env: Env, sender: String) -> StdResult<RemovableByResp> {
+        // whoever RemoveMembers would let `sender` remove, one target at a time
+        let sender = validate_addr(deps, &sender)?;
+        let mut admins = vec![];
+        for (addr, _) in admins_ordered(deps.storage)? {
+            let msg = ExecuteMsg::RemoveMembers {
+                admins: vec![addr.to_string()],
+            };
+            match exec::authorize(deps, &env, &sender, &msg) {
+                Ok(()) => admins.push(addr),
+                Err(ContractError::StdError(err)) => return Err(err),
+                Err(_) => {}
+            }
+        }
+        Ok(RemovableByResp { admins })
+    }
+    pub fn export(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<StateExport> {
+        // admins are paged in storage key order, the rest is repeated on every page
+        let limit = validate_limit(deps, limit.unwrap_or(MAX_LIMIT))?;
+        let start_after = start_after
+            .map(|addr| validate_addr(deps, &addr))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+        let admins = ADMINS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.and_then(|(addr, admin_info)| admin_record(deps.storage, addr, admin_info))
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(StateExport {
+            config: CONFIG.load(deps.storage)?,
+            owner: OWNER.load(deps.storage)?,
+            greeting: GREETING.load(deps.storage)?,
+            admin_count: ADMIN_COUNT.load(deps.storage)?,
+            roster_changes: ROSTER_CHANGES.may_load(deps.storage)?.unwrap_or_default(),
+            admins,
+        })
+    }
+    pub fn delegations(deps: Deps) -> StdResult<DelegationsResp> {
+        let delegations = DELEGATIONS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(admin, (delegate, until))| (admin, delegate, until)))
+            .collect::<StdResult<_>>()?;
+        Ok(DelegationsResp { delegations })
+    }
+    pub fn effective_authority(
+        deps: Deps,
+        env: Env,
+        sender: String,
+    ) -> StdResult<EffectiveAuthorityResp> {
+        // same rules as has_delegated_capability: the delegation has to be running and the
+        // delegating admin still active
+        let sender = validate_addr(deps, &sender)?;
+        if has_capability(deps, &env, &sender, |_| true)? {
+            return Ok(EffectiveAuthorityResp {
+                authority: Some(sender),
+            });
+        }
+        Ok(EffectiveAuthorityResp {
+            authority: delegating_admin(deps, &env, &sender, |_| true)?,
+        })
+    }
+    pub fn payout_plan(deps: Deps, env: Env, funds: Vec<Coin>) -> StdResult<PayoutPlanResp> {
+        // same checks and split as exec::donate_to, with every active admin as a recipient
+        validate_donation(deps, &funds)
+            .map_err(|err| StdError::generic_err(format!("admins::invalid_donation: {err}")))?;
+        let admins: Vec<_> = admins_ordered(deps.storage)?
+            .into_iter()
+            .filter(|(_, admin_info)| is_active(admin_info, &env))
+            .map(|(addr, _)| addr)
+            .collect();
+        let share = split_share(&funds, admins.len());
+
+        let count = Uint128::from(admins.len() as u128);
+        let mut leftover = vec![];
+        for fund in funds {
+            let paid = match share.iter().find(|paid| paid.denom == fund.denom) {
+                Some(paid) => paid.amount.checked_mul(count)?,
+                None => Uint128::zero(),
+            };
+            let amount = fund.amount.checked_sub(paid)?;
+            if !amount.is_zero() {
+                leftover.push(Coin {
+                    denom: fund.denom,
+                    amount,
+                });
+            }
+        }
+        let payouts = if share.is_empty() {
+            vec![]
+        } else {
+            admins
+                .into_iter()
+                .map(|addr| (addr, share.clone()))
+                .collect()
+        };
+        Ok(PayoutPlanResp { payouts, leftover })
+    }
+    pub fn freeze_status(deps: Deps, env: Env) -> StdResult<FreezeStatusResp> {
+        Ok(FreezeStatusResp {
+            frozen: is_frozen(deps, &env)?,
+            freeze_at: FREEZE_AT.may_load(deps.storage)?,
+        })
+    }
+    pub fn status_counts(deps: Deps, env: Env) -> StdResult<StatusCountsResp> {
+        let mut resp = StatusCountsResp {
+            active: 0,
+            expired: 0,
+            pending: 0,
+        };
+        for item in ADMINS.range(deps.storage, None, None, Order::Ascending) {
+            let (_, admin_info) = item?;
+            if is_active(&admin_info, &env) {
+                resp.active += 1;
+            } else {
+                resp.expired += 1;
+            }
+        }
+        // the proposals AcceptAdmin would still take
+        resp.pending = pending_self_accept(deps, env)?.pending.len() as u32;
+        Ok(resp)
+    }
+    pub fn is_bricked(deps: Deps) -> StdResult<IsBrickedResp> {
+        // a configured fallback admin is already in by the time the roster is empty, see
+        // exec::activate_fallback, so an empty roster can't be refilled through any exec
+        let bricked = ADMINS
+            .keys_raw(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_none();
+        Ok(IsBrickedResp { bricked })
+    }
+    pub fn policy(deps: Deps, env: Env) -> StdResult<PolicyResp> {
+        let config = CONFIG.load(deps.storage)?;
+        let role_capabilities = [Role::Super, Role::Moderator]
+            .into_iter()
+            .map(|role| Ok((role, role_capabilities(deps.storage, role)?)))
+            .collect::<StdResult<_>>()?;
+        Ok(PolicyResp {
+            owner: OWNER.load(deps.storage)?,
+            require_acceptance: config.require_acceptance,
+            owner_must_be_admin: config.owner_must_be_admin,
+            default_role: config.default_role,
+            role_capabilities,
+            removal_threshold: config.removal_threshold,
+            max_total_weight: config.max_total_weight,
+            max_admins: config.max_admins,
+            per_admin_cooldown: config.per_admin_cooldown,
+            min_tenure: config.min_tenure,
+            transfer_delay: config.transfer_delay,
+            freeze_delay: config.freeze_delay,
+            proposal_ttl: config.proposal_ttl,
+            rate_guard: config.rate_guard,
+            frozen: is_frozen(deps, &env)?,
+            read_only: READ_ONLY.may_load(deps.storage)?.unwrap_or_default(),
+            paused_until: config
+                .maintenance_until
+                .filter(|until| env.block.time < *until),
+        })
+    }
+    pub fn admins_added_between(
+        deps: Deps,
+        from_height: u64,
+        to_height: u64,
+    ) -> StdResult<AdminsAddedBetweenResp> {
+        let admins = admins_ordered(deps.storage)?
+            .into_iter()
+            .filter(|(_, admin_info)| (from_height..=to_height).contains(&admin_info.joined_height))
+            .map(|(addr, _)| addr)
+            .collect();
+        Ok(AdminsAddedBetweenResp { admins })
+    }
+    pub fn total_donated(deps: Deps) -> StdResult<TotalDonatedResp> {
+        let total = TOTAL_DONATED
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<_>>()?;
+        Ok(TotalDonatedResp { total })
+    }
+    pub fn count(deps: Deps) -> StdResult<CountResp> {
+        let count = ADMIN_COUNT.load(deps.storage)?;
+        Ok(CountResp { count })
+    }
+    pub fn count_exact(deps: Deps) -> StdResult<CountResp> {
+        let count = ADMINS
+            .keys_raw(deps.storage, None, None, Order::Ascending)
+            .count();
+        Ok(CountResp {
+            count: count as u32,
+        })
+    }
+    pub fn admins_by_rank(deps: Deps) -> StdResult<AdminsByRankResp> {
+        // ADMINS ranges in address byte order and the sort is stable, so ties keep that order
+        let mut admins = ADMINS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(addr, admin_info)| (addr, admin_info.rank)))
+            .collect::<StdResult<Vec<_>>>()?;
+        admins.sort_by_key(|(_, rank)| *rank);
+        Ok(AdminsByRankResp { admins })
+    }
+    pub fn pending_removals(deps: Deps, env: Env) -> StdResult<PendingRemovalsResp> {
+        let expired = expired_proposals(deps.storage, &env, &REMOVAL_PROPOSED_AT)?;
+        let removals = PENDING_REMOVALS
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|item| !matches!(item, Ok((addr, _)) if expired.contains(addr)))
+            .collect::<StdResult<_>>()?;
+        Ok(PendingRemovalsResp { removals })
+    }
+    pub fn prunable_proposals(deps: Deps, env: Env) -> StdResult<PrunableProposalsResp> {
+        Ok(PrunableProposalsResp {
+            additions: expired_proposals(deps.storage, &env, &PROPOSED_AT)?,
+            removals: expired_proposals(deps.storage, &env, &REMOVAL_PROPOSED_AT)?,
+        })
+    }
+    pub fn inactive_since(deps: Deps, env: Env, duration: u64) -> StdResult<InactiveSinceResp> {
+        let admins = inactive_admins(deps.storage, &env, duration)?;
+        Ok(InactiveSinceResp { admins })
+    }
+    pub fn can_execute(
+        deps: Deps,
+        env: Env,
+        sender: String,
+        msg: ExecuteMsg,
+    ) -> StdResult<CanExecuteResp> {
+        let sender = validate_addr(deps, &sender)?;
+        let resp = match exec::authorize(deps, &env, &sender, &msg) {
+            Ok(()) => CanExecuteResp {
+                allowed: true,
+                reason: None,
+            },
+            Err(err) => CanExecuteResp {
+                allowed: false,
+                reason: Some(err.to_string()),
+            },
+        };
+        Ok(resp)
+    }
+    pub fn can_join(deps: Deps, env: Env, addr: String) -> StdResult<CanJoinResp> {
+        // the contract-wide gates, then what would stop AddMembers from adding `addr`
+        let addr = validate_addr(deps, &addr)?;
+        let mut reasons = vec![];
+        let add = ExecuteMsg::AddMembers {
+            admins: vec![addr.to_string()],
+        };
+        match exec::ensure_available(deps, &env, &add)
+            .and_then(|()| exec::ensure_enabled(deps, &add))
+        {
+            Ok(()) => {}
+            Err(ContractError::StdError(err)) => return Err(err),
+            Err(err) => reasons.push(err.to_string()),
+        }
+        if ADMINS.has(deps.storage, &addr) {
+            reasons.push(ContractError::AlreadyAdmin { addr }.to_string());
+            return Ok(CanJoinResp {
+                can: false,
+                reasons,
+            });
+        }
+        let config = CONFIG.load(deps.storage)?;
+        if let Some(max) = config.max_admins {
+            if ADMIN_COUNT.load(deps.storage)? >= max {
+                reasons.push(ContractError::TooManyAdmins { max }.to_string());
+            }
+        }
+        if let Some(max) = config.max_total_weight {
+            // the weight AddMembers gives a new admin on top of everyone's
+            let mut total = AdminInfo::new(&env.block, addr.clone()).weight;
+            for item in ADMINS.range(deps.storage, None, None, Order::Ascending) {
+                let (_, admin_info) = item?;
+                total = total.saturating_add(admin_info.weight);
+            }
+            if total > max {
+                reasons.push(ContractError::WeightCapExceeded { max }.to_string());
+            }
+        }
+        if let Err(err) = exec::ensure_min_delegation(deps, &addr) {
+            reasons.push(err.to_string());
+        }
+        if let Err(err) = exec::ensure_allowlisted(deps, &addr) {
+            reasons.push(err.to_string());
+        }
+
+        Ok(CanJoinResp {
+            can: reasons.is_empty(),
+            reasons,
+        })
+    }
+    pub fn block_info(env: Env) -> BlockInfoResp {
+        BlockInfoResp {
+            height: env.block.height,
+            time: env.block.time,
+            chain_id: env.block.chain_id,
+        }
+    }
+    pub fn self_address(env: Env) -> SelfAddressResp {
+        SelfAddressResp {
+            address: env.contract.address,
+        }
+    }
+    pub fn config_matches(deps: Deps, expected: Config) -> StdResult<ConfigMatchesResp> {
+        let config = CONFIG.load(deps.storage)?;
+        let differing = config_diff(&config, expected);
+        Ok(ConfigMatchesResp { differing })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_schema::{schema_for, QueryResponses};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi};
+    use cosmwasm_std::{
+        coin, coins, from_binary, from_slice, to_vec, Addr, Attribute, Empty, FullDelegation,
+        Timestamp, Uint128,
+    };
+    use cw_multi_test::{App, ContractWrapper, Executor};
+    use cw_utils::parse_instantiate_response_data;
+
+    use super::*;
+
+    mod logger { // minimal log contract that records every message it receives
+        use cosmwasm_std::{
+            to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+        };
+        use cw_storage_plus::Item;
+
+        use crate::msg::LogExecuteMsg;
+
+        const LOGS: Item<Vec<LogExecuteMsg>> = Item::new("logs");
+
+        pub fn instantiate(
+            deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            _msg: Empty,
+        ) -> StdResult<Response> {
+            LOGS.save(deps.storage, &vec![])?;
+            Ok(Response::new())
+        }
+
+        pub fn execute(
+            deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            msg: LogExecuteMsg,
+        ) -> StdResult<Response> {
+            LOGS.update(deps.storage, |mut logs| -> StdResult<_> {
+                logs.push(msg);
+                Ok(logs)
+            })?;
+            Ok(Response::new())
+        }
+
+        pub fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+            to_binary(&LOGS.load(deps.storage)?)
+        }
+    }
+
+    mod cw4_group { // minimal cw4-group stand-in that records every membership update it receives
+        use cosmwasm_std::{
+            to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+        };
+        use cw_storage_plus::Item;
+
+        use crate::msg::Cw4ExecuteMsg;
+
+        const UPDATES: Item<Vec<Cw4ExecuteMsg>> = Item::new("updates");
+
+        pub fn instantiate(
+            deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            _msg: Empty,
+        ) -> StdResult<Response> {
+            UPDATES.save(deps.storage, &vec![])?;
+            Ok(Response::new())
+        }
+
+        pub fn execute(
+            deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            msg: Cw4ExecuteMsg,
+        ) -> StdResult<Response> {
+            UPDATES.update(deps.storage, |mut updates| -> StdResult<_> {
+                updates.push(msg);
+                Ok(updates)
+            })?;
+            Ok(Response::new())
+        }
+
+        pub fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+            to_binary(&UPDATES.load(deps.storage)?)
+        }
+    }
+
+    mod notifier { // minimal notifier contract that records every welcome message it receives
+        use cosmwasm_std::{
+            to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+        };
+        use cw_storage_plus::Item;
+
+        use crate::msg::NotifierExecuteMsg;
+
+        const WELCOMES: Item<Vec<NotifierExecuteMsg>> = Item::new("welcomes");
+
+        pub fn instantiate(
+            deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            _msg: Empty,
+        ) -> StdResult<Response> {
+            WELCOMES.save(deps.storage, &vec![])?;
+            Ok(Response::new())
+        }
+
+        pub fn execute(
+            deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            msg: NotifierExecuteMsg,
+        ) -> StdResult<Response> {
+            WELCOMES.update(deps.storage, |mut welcomes| -> StdResult<_> {
+                welcomes.push(msg);
+                Ok(welcomes)
+            })?;
+            Ok(Response::new())
+        }
+
+        pub fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+            to_binary(&WELCOMES.load(deps.storage)?)
+        }
+    }
+
+    mod allowlist { // minimal allowlist contract allowing only the addresses it was instantiated with
+        use cosmwasm_std::{
+            to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+        };
+        use cw_storage_plus::Item;
+
+        use crate::msg::{AllowlistQueryMsg, IsAllowedResp};
+
+        const ALLOWED: Item<Vec<String>> = Item::new("allowed");
+
+        pub fn instantiate(
+            deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            msg: Vec<String>,
+        ) -> StdResult<Response> {
+            ALLOWED.save(deps.storage, &msg)?;
+            Ok(Response::new())
+        }
+
+        pub fn execute(
+            _deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            _msg: Empty,
+        ) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        pub fn query(deps: Deps, _env: Env, msg: AllowlistQueryMsg) -> StdResult<Binary> {
+            let AllowlistQueryMsg::IsAllowed { addr } = msg;
+            let allowed = ALLOWED.load(deps.storage)?.contains(&addr);
+            to_binary(&IsAllowedResp { allowed })
+        }
+    }
+
+    #[test] // This test instantiates the contract with different admins to see if we get the same query 
+    fn instantiation() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![],
+                    ..Default::default()
+                }, // initial message check
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(resp, AdminsListResp { admins: vec![] });
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned(), "admin2".to_owned()], // second message check
+                    ..Default::default()
+                },
+                &[],
+                "Contract 2",
+                None,
+            )
+            .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            resp,
+            AdminsListResp {
+                admins: vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")],
+            }
+        );
+    }
+
+    #[test] // this test wraps our query and verifys that the greet respone matches 
+    fn greet_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp: GreetResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::Greet {})
+            .unwrap();
+
+        assert_eq!(
+            resp,
+            GreetResp {
+                message: "Hello World".to_owned() // message to be matched 
+            }
+        );
+    }
+    #[test] // this test tells us wether our not the address is authorized to execute a message, add or remove addresses and leave as admin
+    fn unauthorized() { 
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr,
+                &ExecuteMsg::AddMembers {
+                    admins: vec!["user".to_owned()],
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("user")
+            },
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test] // this test adds two admins in the same block and checks they are ordered by join time first, then canonical address bytes
+    fn equal_join_time_ordering() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["zed".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(5);
+        });
+
+        app.execute_contract(
+            Addr::unchecked("zed"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["bob".to_owned(), "alice".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+
+        // the mock api's canonical form puts bob first, so the tie is not broken by the address string
+        let canonical = |addr: &str| MockApi::default().addr_canonicalize(addr).unwrap().0;
+        assert!(canonical("bob") < canonical("alice"));
+        assert_eq!(
+            resp,
+            AdminsListResp {
+                admins: vec![
+                    Addr::unchecked("zed"),
+                    Addr::unchecked("bob"),
+                    Addr::unchecked("alice")
+                ],
+            }
+        );
+    }
+
+    #[test] // this test checks the log contract gets a message for every add and leave
+    fn log_contract_receives_mutations() {
+        let mut app = App::default();
+
+        let logger_code = ContractWrapper::new(logger::execute, logger::instantiate, logger::query);
+        let logger_code_id = app.store_code(Box::new(logger_code));
+        let logger_addr = app
+            .instantiate_contract(
+                logger_code_id,
+                Addr::unchecked("owner"),
+                &Empty {},
+                &[],
+                "Logger",
+                None,
+            )
+            .unwrap();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    log_contract: Some(logger_addr.to_string()),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["admin2".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(Addr::unchecked("admin2"), addr, &ExecuteMsg::Leave {}, &[])
+            .unwrap();
+
+        let logs: Vec<LogExecuteMsg> = app
+            .wrap()
+            .query_wasm_smart(logger_addr, &Empty {})
+            .unwrap();
+
+        assert_eq!(
+            logs,
+            vec![
+                LogExecuteMsg::Log {
+                    action: "add_members".to_owned(),
+                    sender: Addr::unchecked("admin1"),
+                    affected: vec![Addr::unchecked("admin2")],
+                },
+                LogExecuteMsg::Log {
+                    action: "leave".to_owned(),
+                    sender: Addr::unchecked("admin2"),
+                    affected: vec![Addr::unchecked("admin2")],
+                },
+            ]
+        );
+    }
+
+    #[test] // this test reconciles the admins against a partially overlapping roster
+    fn reconcile_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        "admin1".to_owned(),
+                        "admin2".to_owned(),
+                        "admin3".to_owned(),
+                    ],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp: ReconcileResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::Reconcile {
+                    expected: vec![
+                        "admin2".to_owned(),
+                        "admin3".to_owned(),
+                        "admin4".to_owned(),
+                        "admin4".to_owned(), // duplicates are ignored
+                    ],
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            resp,
+            ReconcileResp {
+                missing: vec![Addr::unchecked("admin4")],
+                extra: vec![Addr::unchecked("admin1")],
+            }
+        );
+    }
+
+    #[test] // this test checks an admin can change the greeting when it is not locked
+    fn set_greeting_unlocked() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::SetGreeting {
+                greeting: "Hello Cosmos".to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: GreetResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::Greet {})
+            .unwrap();
+
+        assert_eq!(
+            resp,
+            GreetResp {
+                message: "Hello Cosmos".to_owned()
+            }
+        );
+    }
+
+    #[test] // this test checks a locked greeting rejects updates even from an admin
+    fn set_greeting_locked() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    greeting_locked: true,
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::SetGreeting {
+                    greeting: "Hello Cosmos".to_owned(),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(ContractError::Frozen {}, err.downcast().unwrap());
+
+        let resp: GreetResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::Greet {})
+            .unwrap();
+
+        assert_eq!(
+            resp,
+            GreetResp {
+                message: "Hello World".to_owned()
+            }
+        );
+    }
+
+    #[test] // this test asks for a preview smaller than the admin set and checks the sample and total
+    fn admins_preview_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        "admin1".to_owned(),
+                        "admin2".to_owned(),
+                        "admin3".to_owned(),
+                        "admin4".to_owned(),
+                    ],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp: AdminsPreviewResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::AdminsPreview { limit: 2 })
+            .unwrap();
+
+        assert_eq!(
+            resp,
+            AdminsPreviewResp {
+                sample: vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")],
+                total: 4,
+            }
+        );
+    }
+
+    #[test] // this test checks an invalid pagination limit returns the prefixed error message
+    fn invalid_limit_error() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .wrap()
+            .query_wasm_smart::<AdminsPreviewResp>(addr, &QueryMsg::AdminsPreview { limit: 0 })
+            .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("admins::invalid_limit: limit must be greater than zero"));
+    }
+
+    #[test] // this test checks a limit above the maximum is silently clamped by default
+    fn admins_list_limit_clamped() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: (0..105).map(|i| format!("admin{i:03}")).collect(),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: Some(150),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(resp.admins.len(), 100);
+    }
+
+    #[test] // this test checks a limit above the maximum is rejected when strict limits are on
+    fn admins_list_limit_strict() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: (0..105).map(|i| format!("admin{i:03}")).collect(),
+                    strict_limits: true,
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .wrap()
+            .query_wasm_smart::<AdminsListResp>(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: Some(150),
+                },
+            )
+            .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("admins::limit_too_large: limit must not exceed 100"));
+    }
+
+    #[test] // this test donates a denom to the contract, sweeps it to a treasury and checks the transfer
+    fn sweep() {
+        let mut app = App::new(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("donor"), coins(100, "atom"))
+                .unwrap();
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.send_tokens(Addr::unchecked("donor"), addr.clone(), &coins(100, "atom"))
+            .unwrap();
+
+        let sweep = ExecuteMsg::Sweep {
+            denom: "atom".to_owned(),
+            to: "treasury".to_owned(),
+        };
+
+        let err = app
+            .execute_contract(Addr::unchecked("admin1"), addr.clone(), &sweep, &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotOwner {
+                sender: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(Addr::unchecked("owner"), addr.clone(), &sweep, &[])
+            .unwrap();
+
+        assert_eq!(
+            app.wrap()
+                .query_balance("treasury", "atom")
+                .unwrap()
+                .amount,
+            Uint128::new(100)
+        );
+        assert_eq!(
+            app.wrap().query_balance(&addr, "atom").unwrap().amount,
+            Uint128::zero()
+        );
+
+        let err = app
+            .execute_contract(Addr::unchecked("owner"), addr, &sweep, &[])
+            .unwrap_err();
+        assert_eq!(ContractError::NothingToSweep {}, err.downcast().unwrap());
+    }
+
+    #[test] // this test creates an invite, redeems it with the secret code and checks it can't be reused
+    fn create_and_redeem_invite() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::CreateInvite {
+                code_hash: Binary::from(Sha256::digest(b"secret").to_vec()),
+                role: Role::Moderator,
+                weight: 3,
+                expires_at: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("newbie"),
+            addr.clone(),
+            &ExecuteMsg::RedeemInvite {
+                code: "secret".to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let raw = app
+            .wrap()
+            .query_wasm_raw(&addr, ADMINS.key(&Addr::unchecked("newbie")).to_vec())
+            .unwrap()
+            .unwrap();
+        let admin_info: AdminInfo = from_slice(&raw).unwrap();
+        assert_eq!(admin_info.role, Role::Moderator);
+        assert_eq!(admin_info.weight, 3);
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("other"),
+                addr,
+                &ExecuteMsg::RedeemInvite {
+                    code: "secret".to_owned(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::InviteNotFound {}, err.downcast().unwrap());
+    }
+
+    #[test] // this test creates two invites and lists them without exposing the codes
+    fn invites_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let hash1 = Binary::from(Sha256::digest(b"first").to_vec());
+        let hash2 = Binary::from(Sha256::digest(b"second").to_vec());
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::CreateInvite {
+                code_hash: hash1.clone(),
+                role: Role::Super,
+                weight: 1,
+                expires_at: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::CreateInvite {
+                code_hash: hash2.clone(),
+                role: Role::Moderator,
+                weight: 2,
+                expires_at: Some(1000),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: InvitesResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::Invites {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+
+        let mut expected = vec![
+            InviteEntry {
+                code_hash: hash1,
+                role: Role::Super,
+                weight: 1,
+                expires_at: None,
+            },
+            InviteEntry {
+                code_hash: hash2,
+                role: Role::Moderator,
+                weight: 2,
+                expires_at: Some(1000),
+            },
+        ];
+        expected.sort_by(|a, b| a.code_hash.as_slice().cmp(b.code_hash.as_slice())); // invites are listed by hash
+        assert_eq!(resp, InvitesResp { invites: expected });
+    }
+
+    #[test] // this test checks an expiry of 0 means the admin never expires
+    fn expiry_zero_is_active() {
+        let mut env = mock_env();
+        let admin_info = AdminInfo::new(&env.block, Addr::unchecked("owner"));
+
+        assert!(is_active(&admin_info, &env));
+        env.block.height = u64::MAX;
+        assert!(is_active(&admin_info, &env));
+    }
+
+    #[test] // this test checks an admin with an expiry far in the future is still active
+    fn expiry_far_future_is_active() {
+        let env = mock_env();
+        let admin_info = AdminInfo {
+            expires_at: u64::MAX,
+            ..AdminInfo::new(&env.block, Addr::unchecked("owner"))
+        };
+
+        assert!(is_active(&admin_info, &env));
+    }
+
+    #[test] // this test checks an admin is inactive from their expiry height onwards
+    fn expiry_passed_is_inactive() {
+        let mut env = mock_env();
+        let admin_info = AdminInfo {
+            expires_at: env.block.height,
+            ..AdminInfo::new(&env.block, Addr::unchecked("owner"))
+        };
+
+        assert!(!is_active(&admin_info, &env));
+        env.block.height += 1;
+        assert!(!is_active(&admin_info, &env));
+    }
+
+    #[test] // this test sets several annotations on an admin, clears them and checks none are left
+    fn clear_annotations() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let annotations = [
+            ("team", "core"),
+            ("timezone", "utc"),
+            ("discord", "admin2#1"),
+        ];
+        for (expected_version, (key, value)) in (0..).zip(annotations) {
+            app.execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::SetAnnotation {
+                    addr: "admin2".to_owned(),
+                    key: key.to_owned(),
+                    value: value.to_owned(),
+                    expected_version,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
+        let resp: AnnotationsResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::Annotations {
+                    addr: "admin2".to_owned(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.annotations.len(), 3);
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::ClearAnnotations {
+                    addr: "admin2".to_owned(),
+                    expected_version: 3,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotOwner {
+                sender: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("admin2"),
+            addr.clone(),
+            &ExecuteMsg::ClearAnnotations {
+                addr: "admin2".to_owned(),
+                expected_version: 3,
+            },
+            &[],
+        )
+        .unwrap();
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin2"),
+                addr.clone(),
+                &ExecuteMsg::ClearAnnotations {
+                    addr: "admin2".to_owned(),
+                    expected_version: 3,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::StaleUpdate {
+                expected: 3,
+                actual: 4
+            },
+            err.downcast().unwrap()
+        );
+
+        let resp: AnnotationsResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::Annotations {
+                    addr: "admin2".to_owned(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp, AnnotationsResp { annotations: vec![] });
+    }
+
+    #[test] // this test checks the cw4 group receives a membership update on instantiate, add and leave
+    fn cw4_group_mirrors_roster() {
+        let mut app = App::default();
+
+        let group_code =
+            ContractWrapper::new(cw4_group::execute, cw4_group::instantiate, cw4_group::query);
+        let group_code_id = app.store_code(Box::new(group_code));
+        let group_addr = app
+            .instantiate_contract(
+                group_code_id,
+                Addr::unchecked("owner"),
+                &Empty {},
+                &[],
+                "Group",
+                None,
+            )
+            .unwrap();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    cw4_group: Some(group_addr.to_string()),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["admin2".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(Addr::unchecked("admin2"), addr, &ExecuteMsg::Leave {}, &[])
+            .unwrap();
+
+        let updates: Vec<Cw4ExecuteMsg> =
+            app.wrap().query_wasm_smart(group_addr, &Empty {}).unwrap();
+
+        assert_eq!(
+            updates,
+            vec![
+                Cw4ExecuteMsg::UpdateMembers {
+                    remove: vec![],
+                    add: vec![Cw4Member {
+                        addr: "admin1".to_owned(),
+                        weight: 1,
+                    }],
+                },
+                Cw4ExecuteMsg::UpdateMembers {
+                    remove: vec![],
+                    add: vec![Cw4Member {
+                        addr: "admin2".to_owned(),
+                        weight: 1,
+                    }],
+                },
+                Cw4ExecuteMsg::UpdateMembers {
+                    remove: vec!["admin2".to_owned()],
+                    add: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test] // this test mixes super admins and moderators and checks they are grouped by role
+    fn admins_by_role_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        for (code, moderator) in [("code1", "mod1"), ("code2", "mod2")] {
+            app.execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::CreateInvite {
+                    code_hash: Binary::from(Sha256::digest(code.as_bytes()).to_vec()),
+                    role: Role::Moderator,
+                    weight: 1,
+                    expires_at: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+            app.execute_contract(
+                Addr::unchecked(moderator),
+                addr.clone(),
+                &ExecuteMsg::RedeemInvite {
+                    code: code.to_owned(),
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
+        let resp: AdminsByRoleResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::AdminsByRole {})
+            .unwrap();
+
+        assert_eq!(
+            resp,
+            AdminsByRoleResp {
+                roles: vec![
+                    (
+                        Role::Super,
+                        vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")]
+                    ),
+                    (
+                        Role::Moderator,
+                        vec![Addr::unchecked("mod2"), Addr::unchecked("mod1")]
+                    ),
+                ],
+            }
+        );
+    }
+
+    #[test] // this test removes one present and one absent address and decodes the result data
+    fn remove_members_result() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::RemoveMembers {
+                    admins: vec!["admin2".to_owned(), "stranger".to_owned()],
+                },
+                &[],
+            )
+            .unwrap();
+
+        let result: RemoveResult = from_binary(&resp.data.unwrap()).unwrap();
+        assert_eq!(
+            result,
+            RemoveResult {
+                removed: vec![Addr::unchecked("admin2")],
+                skipped: vec![Addr::unchecked("stranger")],
+            }
+        );
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp,
+            AdminsListResp {
+                admins: vec![Addr::unchecked("admin1")],
+            }
+        );
+    }
+
+    #[test] // this test mocks the staking querier and checks only well-staked addresses can be added
+    fn min_delegation() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_staking(
+            "ustake",
+            &[],
+            &[
+                FullDelegation {
+                    delegator: Addr::unchecked("whale"),
+                    validator: "validator".to_owned(),
+                    amount: coin(100, "ustake"),
+                    can_redelegate: coin(100, "ustake"),
+                    accumulated_rewards: vec![],
+                },
+                FullDelegation {
+                    delegator: Addr::unchecked("minnow"),
+                    validator: "validator".to_owned(),
+                    amount: coin(10, "ustake"),
+                    can_redelegate: coin(10, "ustake"),
+                    accumulated_rewards: vec![],
+                },
+                FullDelegation {
+                    delegator: Addr::unchecked("minnow"),
+                    validator: "validator".to_owned(),
+                    amount: coin(100, "uother"), // not the bonded denom, doesn't count
+                    can_redelegate: coin(100, "uother"),
+                    accumulated_rewards: vec![],
+                },
+            ],
+        );
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                min_delegation: Some(Uint128::new(50)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["minnow".to_owned()],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InsufficientStake {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["whale".to_owned()],
+            },
+        )
+        .unwrap();
+        assert!(ADMINS.has(&deps.storage, &Addr::unchecked("whale")));
+
+        // joining by invite or by accepting a proposal is held to the same minimum
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::CreateInvite {
+                code_hash: Binary::from(Sha256::digest(b"secret").to_vec()),
+                role: Role::Moderator,
+                weight: 1,
+                expires_at: None,
+            },
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("minnow", &[]),
+            ExecuteMsg::RedeemInvite {
+                code: "secret".to_owned(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InsufficientStake {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::ProposeAdmin {
+                addr: "minnow".to_owned(),
+            },
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("minnow", &[]),
+            ExecuteMsg::AcceptAdmin {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InsufficientStake {});
+        assert!(!ADMINS.has(&deps.storage, &Addr::unchecked("minnow")));
+    }
+
+    #[test] // this test checks IndexOf matches the admins' positions in the canonical ordering
+    fn index_of_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["zed".to_owned(), "yan".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(5);
+        });
+
+        app.execute_contract(
+            Addr::unchecked("zed"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["bob".to_owned(), "alice".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let list: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(list.admins.len(), 4);
+
+        for (expected, admin) in list.admins.iter().enumerate() {
+            let resp: IndexOfResp = app
+                .wrap()
+                .query_wasm_smart(
+                    addr.clone(),
+                    &QueryMsg::IndexOf {
+                        addr: admin.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(resp.index, Some(expected as u32));
+        }
+
+        let resp: IndexOfResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::IndexOf {
+                    addr: "stranger".to_owned(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp, IndexOfResp { index: None });
+    }
+
+    #[test] // this test adds admins through the string-only path and checks they get the configured default role
+    fn default_role_for_add_members() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    default_role: Some(Role::Moderator),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["mod1".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                default_role: Some(Role::Super),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["super1".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: AdminsByRoleResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::AdminsByRole {})
+            .unwrap();
+
+        assert_eq!(
+            resp,
+            AdminsByRoleResp {
+                roles: vec![
+                    (
+                        Role::Super,
+                        vec![Addr::unchecked("admin1"), Addr::unchecked("super1")]
+                    ),
+                    (Role::Moderator, vec![Addr::unchecked("mod1")]),
+                ],
+            }
+        );
+    }
+
+    #[test] // this test checks the fingerprint is stable across queries and changes after an add
+    fn state_fingerprint_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let first: StateFingerprintResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::StateFingerprint {})
+            .unwrap();
+        let second: StateFingerprintResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::StateFingerprint {})
+            .unwrap();
+        assert_eq!(first, second);
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["admin2".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let after_add: StateFingerprintResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::StateFingerprint {})
+            .unwrap();
+        assert_ne!(first, after_add);
+    }
+
+    #[test] // this test checks empty and whitespace-only admin strings are rejected on instantiate and add
+    fn empty_admin_address() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let err = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned(), "".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidAddress {
+                addr: "".to_owned()
+            },
+            err.downcast().unwrap()
+        );
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr,
+                &ExecuteMsg::AddMembers {
+                    admins: vec!["   ".to_owned()],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidAddress {
+                addr: "   ".to_owned()
+            },
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test] // this test proposes a new owner, fails an early accept, waits out the delay and accepts
+    fn ownership_transfer_timelock() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    transfer_delay: 100,
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::TransferOwnership {
+                new_owner: "new_owner".to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let ready_at = app.block_info().time.plus_seconds(100);
+        let resp: PendingOwnerResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::PendingOwnerReadyAt {})
+            .unwrap();
+        assert_eq!(
+            resp,
+            PendingOwnerResp {
+                pending_owner: Some(Addr::unchecked("new_owner")),
+                ready_at: Some(ready_at),
+            }
+        );
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("new_owner"),
+                addr.clone(),
+                &ExecuteMsg::AcceptOwnership {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::TransferNotReady { ready_at },
+            err.downcast().unwrap()
+        );
+
+        app.update_block(|block| {
+            block.height += 20;
+            block.time = block.time.plus_seconds(100);
+        });
+
+        app.execute_contract(
+            Addr::unchecked("new_owner"),
+            addr.clone(),
+            &ExecuteMsg::AcceptOwnership {},
+            &[],
+        )
+        .unwrap();
+
+        // owner-only operations now work for the new owner and not the old one
+        app.execute_contract(
+            Addr::unchecked("new_owner"),
+            addr.clone(),
+            &ExecuteMsg::UpdateConfig { default_role: None },
+            &[],
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr,
+                &ExecuteMsg::UpdateConfig { default_role: None },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotOwner {
+                sender: Addr::unchecked("owner")
+            },
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test] // this test adds an admin, rejects an early leave, waits out the tenure and leaves
+    fn min_tenure_leave() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    min_tenure: Some(3600),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["admin2".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let ready_at = app.block_info().time.plus_seconds(3600);
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin2"),
+                addr.clone(),
+                &ExecuteMsg::Leave {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::TenureNotMet { ready_at },
+            err.downcast().unwrap()
+        );
+
+        app.update_block(|block| {
+            block.height += 720;
+            block.time = block.time.plus_seconds(3600);
+        });
+
+        app.execute_contract(
+            Addr::unchecked("admin2"),
+            addr.clone(),
+            &ExecuteMsg::Leave {},
+            &[],
+        )
+        .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp,
+            AdminsListResp {
+                admins: vec![Addr::unchecked("admin1")],
+            }
+        );
+    }
+
+    #[test] // this test sets admins at various expiry heights and checks only the soon-to-expire ones are listed
+    fn expiring_soon_query() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec![
+                    "soon".to_owned(),
+                    "later".to_owned(),
+                    "never".to_owned(),
+                    "expired".to_owned(),
+                    "sooner".to_owned(),
+                ],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let height = env.block.height;
+        for (addr, expires_at) in [
+            ("soon", height + 10),
+            ("later", height + 500),
+            ("never", 0),
+            ("expired", height),
+            ("sooner", height + 3),
+        ] {
+            ADMINS
+                .update(
+                    &mut deps.storage,
+                    &Addr::unchecked(addr),
+                    |admin_info| -> StdResult<_> {
+                        let mut admin_info = admin_info.unwrap();
+                        admin_info.expires_at = expires_at;
+                        Ok(admin_info)
+                    },
+                )
+                .unwrap();
+        }
+
+        let resp: ExpiringSoonResp = from_binary(
+            &query(
+                deps.as_ref(),
+                env,
+                QueryMsg::ExpiringSoon { within_blocks: 10 },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            resp,
+            ExpiringSoonResp {
+                admins: vec![
+                    (Addr::unchecked("sooner"), height + 3),
+                    (Addr::unchecked("soon"), height + 10),
+                ],
+            }
+        );
+    }
+
+    #[test] // this test extends an admin's expiry and checks they can still act past the original height
+    fn extend_expiry() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let original = env.block.height + 10;
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            ExecuteMsg::ExtendExpiry {
+                addr: "admin1".to_owned(),
+                new_expires_at: original,
+            },
+        )
+        .unwrap_err(); // admin1 never expires, so any expiry height would shorten it
+
+        ADMINS
+            .update(
+                &mut deps.storage,
+                &Addr::unchecked("admin1"),
+                |admin_info| -> StdResult<_> {
+                    let mut admin_info = admin_info.unwrap();
+                    admin_info.expires_at = original;
+                    Ok(admin_info)
+                },
+            )
+            .unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            ExecuteMsg::ExtendExpiry {
+                addr: "admin1".to_owned(),
+                new_expires_at: original - 1,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidExpiry {});
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            ExecuteMsg::ExtendExpiry {
+                addr: "admin1".to_owned(),
+                new_expires_at: original + 100,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            ADMINS
+                .load(&deps.storage, &Addr::unchecked("admin1"))
+                .unwrap()
+                .version,
+            1
+        );
+
+        env.block.height = original + 20;
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info("admin1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["admin2".to_owned()],
+            },
+        )
+        .unwrap();
+        assert!(ADMINS.has(&deps.storage, &Addr::unchecked("admin2")));
+    }
+    #[test] // this test checks that every message type produces a titled schema and each query has a response schema
+    fn message_schemas() {
+        let titles = [
+            schema_for!(InstantiateMsg),
+            schema_for!(ExecuteMsg),
+            schema_for!(QueryMsg),
+            schema_for!(MigrateMsg),
+            schema_for!(SudoMsg),
+        ]
+        .map(|schema| schema.schema.metadata.unwrap().title.unwrap());
+        assert_eq!(
+            titles,
+            [
+                "InstantiateMsg",
+                "ExecuteMsg",
+                "QueryMsg",
+                "MigrateMsg",
+                "SudoMsg"
+            ]
+        );
+
+        let responses = QueryMsg::response_schemas().unwrap();
+        assert_eq!(responses.len(), 43);
+    }
+    #[test] // this test checks BothAdmins reports each address separately and only sets both when they are both admins
+    fn both_admins_query() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let resp: BothAdminsResp = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::BothAdmins {
+                    a: "admin1".to_owned(),
+                    b: "stranger".to_owned(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            resp,
+            BothAdminsResp {
+                a_is_admin: true,
+                b_is_admin: false,
+                both: false,
+            }
+        );
+
+        let err = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::BothAdmins {
+                a: "admin1".to_owned(),
+                b: "".to_owned(),
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("admins::invalid_address"));
+    }
+    #[test] // this test checks adds are rejected during a maintenance window and work again once it has passed
+    fn maintenance_window() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let until = app.block_info().time.plus_seconds(100);
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetMaintenance { until: Some(until) },
+            &[],
+        )
+        .unwrap();
+
+        let add = ExecuteMsg::AddMembers {
+            admins: vec!["admin2".to_owned()],
+        };
+        let err = app
+            .execute_contract(Addr::unchecked("admin1"), addr.clone(), &add, &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Maintenance { until },
+            err.downcast().unwrap()
+        );
+
+        app.update_block(|block| {
+            block.height += 20;
+            block.time = block.time.plus_seconds(100);
+        });
+
+        app.execute_contract(Addr::unchecked("admin1"), addr.clone(), &add, &[])
+            .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")]
+        );
+    }
+    #[test] // this test migrates a contract without a stored owner and checks the backfilled owner can use owner-only execs
+    fn migrate_backfills_owner() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("deployer", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        OWNER.remove(&mut deps.storage); // the step that added OWNER before there was a migrate for it
+
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+        assert_eq!(err, ContractError::OwnerRequired {});
+
+        let err = migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                owner: Some("stranger".to_owned()),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotAdmin {
+                addr: Addr::unchecked("stranger")
+            }
+        );
+
+        migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                owner: Some("admin1".to_owned()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            OWNER.load(&deps.storage).unwrap(),
+            Addr::unchecked("admin1")
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::UpdateConfig {
+                default_role: Some(Role::Moderator),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            CONFIG.load(&deps.storage).unwrap().default_role,
+            Role::Moderator
+        );
+    }
+    #[test] // this test exports the roster into a second instance and checks the first one is frozen afterwards
+    fn export_roster_to_new_instance() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let old = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Old",
+                None,
+            )
+            .unwrap();
+
+        app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(5);
+        });
+
+        let new = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    migration_source: Some(old.to_string()),
+                    ..Default::default()
+                },
+                &[],
+                "New",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                old.clone(),
+                &ExecuteMsg::ExportRoster {
+                    to: old.to_string(),
+                    freeze: true,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidAddress {
+                addr: old.to_string()
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            old.clone(),
+            &ExecuteMsg::ExportRoster {
+                to: new.to_string(),
+                freeze: true,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let old_list: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                old.clone(),
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        let new_list: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                new,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(new_list, old_list);
+        assert_eq!(new_list.admins.len(), 2);
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                old,
+                &ExecuteMsg::AddMembers {
+                    admins: vec!["admin3".to_owned()],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::Frozen {}, err.downcast().unwrap());
+    }
+    #[test] // this test imports a roster into a fresh instance and checks it is restored verbatim and only once
+    fn import_roster_into_fresh_instance() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                migration_source: Some("old_contract".to_owned()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let record = AdminRecord {
+            addr: "admin1".to_owned(),
+            joined: Timestamp::from_seconds(42),
+            role: Role::Moderator,
+            weight: 7,
+            expires_at: 12_345,
+            added_by: "founder".to_owned(),
+            label: None,
+            annotations: vec![("team".to_owned(), "infra".to_owned())],
+            rank: 3,
+            version: 5,
+            joined_height: 7,
+        };
+        let import = ExecuteMsg::ImportRoster {
+            admins: vec![record],
+        };
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            import.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("owner")
+            }
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("old_contract", &[]),
+            import.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            ADMINS.load(&deps.storage, &Addr::unchecked("admin1")).unwrap(),
+            AdminInfo {
+                joined: Timestamp::from_seconds(42),
+                joined_height: 7,
+                role: Role::Moderator,
+                weight: 7,
+                expires_at: 12_345,
+                added_by: Addr::unchecked("founder"),
+                rank: 3,
+                version: 5,
+            }
+        );
+        assert_eq!(ADMIN_COUNT.load(&deps.storage).unwrap(), 1);
+
+        let resp: AnnotationsResp = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Annotations {
+                    addr: "admin1".to_owned(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            resp.annotations,
+            vec![("team".to_owned(), "infra".to_owned())]
+        );
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("old_contract", &[]),
+            import,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::AlreadyPopulated {});
+    }
+    #[test] // this test checks the per-admin cooldown throttles one admin while another can still act
+    fn per_admin_cooldown() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                    per_admin_cooldown: Some(60),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let add = |admin: &str| ExecuteMsg::AddMembers {
+            admins: vec![admin.to_owned()],
+        };
+        app.execute_contract(Addr::unchecked("admin1"), addr.clone(), &add("admin3"), &[])
+            .unwrap();
+
+        let err = app
+            .execute_contract(Addr::unchecked("admin1"), addr.clone(), &add("admin4"), &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Cooldown {
+                ready_at: app.block_info().time.plus_seconds(60)
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(Addr::unchecked("admin2"), addr.clone(), &add("admin4"), &[])
+            .unwrap();
+
+        app.update_block(|block| {
+            block.height += 12;
+            block.time = block.time.plus_seconds(60);
+        });
+
+        app.execute_contract(Addr::unchecked("admin1"), addr.clone(), &add("admin5"), &[])
+            .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.admins.len(), 5);
+    }
+    #[test] // this test checks CreatedAt reports the block the contract was instantiated in
+    fn created_at_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        app.update_block(|block| {
+            block.height += 3;
+            block.time = block.time.plus_seconds(15);
+        });
+        let block = app.block_info();
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg::default(),
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(5);
+        });
+
+        let resp: CreatedAtResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::CreatedAt {})
+            .unwrap();
+        assert_eq!(
+            resp,
+            CreatedAtResp {
+                height: block.height,
+                time: block.time,
+            }
+        );
+    }
+    #[test] // this test decodes the typed instantiate response data as a factory contract would
+    fn instantiate_response_data() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let resp = app
+            .execute(
+                Addr::unchecked("owner"),
+                WasmMsg::Instantiate {
+                    admin: None,
+                    code_id,
+                    msg: to_binary(&InstantiateMsg {
+                        admins: vec![
+                            "admin1".to_owned(),
+                            "admin2".to_owned(),
+                            "admin1".to_owned(),
+                        ],
+                        ..Default::default()
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                    label: "Contract".to_owned(),
+                }
+                .into(),
+            )
+            .unwrap();
+
+        let data = parse_instantiate_response_data(&resp.data.unwrap())
+            .unwrap()
+            .data
+            .unwrap();
+        let result: InstantiateResult = from_binary(&data).unwrap();
+        assert_eq!(
+            result,
+            InstantiateResult {
+                owner: Addr::unchecked("owner"),
+                admin_count: 2,
+            }
+        );
+    }
+    #[test] // this test checks Availability reports the maintenance window and clears once it has passed
+    fn availability_during_maintenance() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let until = app.block_info().time.plus_seconds(100);
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetMaintenance { until: Some(until) },
+            &[],
+        )
+        .unwrap();
+
+        let resp: AvailabilityResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::Availability {})
+            .unwrap();
+        assert_eq!(
+            resp,
+            AvailabilityResp {
+                can_add: false,
+                can_remove: false,
+                can_leave: false,
+                reasons: ["can_add", "can_remove", "can_leave"]
+                    .map(|flag| format!("{flag}: contract is under maintenance until {until}"))
+                    .to_vec(),
+            }
+        );
+
+        app.update_block(|block| {
+            block.height += 20;
+            block.time = block.time.plus_seconds(100);
+        });
+
+        let resp: AvailabilityResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::Availability {})
+            .unwrap();
+        assert!(resp.can_add && resp.can_remove && resp.can_leave);
+        assert!(resp.reasons.is_empty());
+    }
+    #[test] // this test checks AddMembers only accepts new admins the allowlist contract permits
+    fn allowlist_contract() {
+        let mut app = App::default();
+
+        let allowlist =
+            ContractWrapper::new(allowlist::execute, allowlist::instantiate, allowlist::query);
+        let allowlist_id = app.store_code(Box::new(allowlist));
+        let allowlist_addr = app
+            .instantiate_contract(
+                allowlist_id,
+                Addr::unchecked("owner"),
+                &vec!["good".to_owned()],
+                &[],
+                "Allowlist",
+                None,
+            )
+            .unwrap();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    allowlist_contract: Some(allowlist_addr.to_string()),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::AddMembers {
+                    admins: vec!["bad".to_owned()],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotAllowlisted {
+                addr: Addr::unchecked("bad")
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["good".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![Addr::unchecked("admin1"), Addr::unchecked("good")]
+        );
+    }
+    #[test] // this test annotates an admin, removes them and checks none of their entries are left behind
+    fn removal_leaves_no_orphans() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                per_admin_cooldown: Some(1),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        for (sender, target) in [("admin1", "admin2"), ("admin2", "admin1")] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(sender, &[]),
+                ExecuteMsg::SetAnnotation {
+                    addr: target.to_owned(),
+                    key: "team".to_owned(),
+                    value: "infra".to_owned(),
+                    expected_version: 0,
+                },
+            )
+            .unwrap();
+        }
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(1);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::RemoveMembers {
+                admins: vec!["admin2".to_owned()],
+            },
+        )
+        .unwrap();
+        env.block.time = env.block.time.plus_seconds(1);
+        execute(deps.as_mut(), env, mock_info("admin1", &[]), ExecuteMsg::Leave {}).unwrap();
+
+        for admin in ["admin1", "admin2"] {
+            let admin = Addr::unchecked(admin);
+            assert!(!ADMINS.has(&deps.storage, &admin));
+            assert!(!LAST_ACTION.has(&deps.storage, &admin));
+            assert_eq!(
+                ANNOTATIONS
+                    .prefix(&admin)
+                    .keys(&deps.storage, None, None, Order::Ascending)
+                    .count(),
+                0
+            );
+        }
+        assert_eq!(ADMIN_COUNT.load(&deps.storage).unwrap(), 0);
+    }
+    #[test] // this test checks CheckIntegrity passes after normal operations and reports a drifted counter
+    fn check_integrity_query() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["admin3".to_owned()],
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::SetAnnotation {
+                addr: "admin2".to_owned(),
+                key: "team".to_owned(),
+                value: "infra".to_owned(),
+                expected_version: 0,
+            },
+        )
+        .unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("admin2", &[]), ExecuteMsg::Leave {}).unwrap();
+
+        let check = |deps: Deps| -> IntegrityResp {
+            from_binary(&query(deps, mock_env(), QueryMsg::CheckIntegrity {}).unwrap()).unwrap()
+        };
+        assert_eq!(
+            check(deps.as_ref()),
+            IntegrityResp {
+                ok: true,
+                problems: vec![],
+            }
+        );
+
+        ADMIN_COUNT.save(&mut deps.storage, &5).unwrap();
+        assert_eq!(
+            check(deps.as_ref()),
+            IntegrityResp {
+                ok: false,
+                problems: vec!["admin_count is 5 but 2 admins are stored".to_owned()],
+            }
+        );
+    }
+    #[test] // this test donates to two of three admins and checks only they receive a share
+    fn donate_to_recipients() {
+        let mut app = App::new(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("donor"), coins(101, "atom"))
+                .unwrap();
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        "admin1".to_owned(),
+                        "admin2".to_owned(),
+                        "admin3".to_owned(),
+                    ],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("donor"),
+                addr.clone(),
+                &ExecuteMsg::DonateTo {
+                    recipients: vec!["admin1".to_owned(), "stranger".to_owned()],
+                },
+                &coins(101, "atom"),
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotAdmin {
+                addr: Addr::unchecked("stranger")
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("donor"),
+            addr.clone(),
+            &ExecuteMsg::DonateTo {
+                recipients: vec!["admin1".to_owned(), "admin3".to_owned()],
+            },
+            &coins(101, "atom"),
+        )
+        .unwrap();
+
+        let balance = |who: &str| app.wrap().query_balance(who, "atom").unwrap().amount;
+        assert_eq!(balance("admin1"), Uint128::new(50));
+        assert_eq!(balance("admin2"), Uint128::zero());
+        assert_eq!(balance("admin3"), Uint128::new(50));
+        assert_eq!(balance(addr.as_str()), Uint128::new(1));
+    }
+    #[test] // this test proposes an admin, checks nobody else can accept for them, then lets them self-accept
+    fn propose_and_self_accept() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    require_acceptance: true,
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::AddMembers {
+                    admins: vec!["newbie".to_owned()],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::AcceptanceRequired {}, err.downcast().unwrap());
+        let err = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::MergeFrom {
+                    other: "other".to_owned(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::AcceptanceRequired {},
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::ProposeAdmin {
+                addr: "newbie".to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: PendingSelfAcceptResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::PendingSelfAccept {})
+            .unwrap();
+        assert_eq!(
+            resp.pending,
+            vec![(Addr::unchecked("newbie"), Addr::unchecked("admin1"))]
+        );
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("mallory"),
+                addr.clone(),
+                &ExecuteMsg::AcceptAdmin {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotProposed {
+                sender: Addr::unchecked("mallory")
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("newbie"),
+            addr.clone(),
+            &ExecuteMsg::AcceptAdmin {},
+            &[],
+        )
+        .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![Addr::unchecked("newbie"), Addr::unchecked("admin1")]
+        );
+        let resp: PendingSelfAcceptResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::PendingSelfAccept {})
+            .unwrap();
+        assert!(resp.pending.is_empty());
+    }
+    #[test] // this test checks AddedBy reports the owner for initial admins and the adding admin afterwards
+    fn added_by_query() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["admin2".to_owned()],
+            },
+        )
+        .unwrap();
+
+        let added_by = |addr: &str| -> Option<Addr> {
+            let resp: AddedByResp = from_binary(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::AddedBy {
+                        addr: addr.to_owned(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            resp.added_by
+        };
+        assert_eq!(added_by("admin1"), Some(Addr::unchecked("owner")));
+        assert_eq!(added_by("admin2"), Some(Addr::unchecked("admin1")));
+        assert_eq!(added_by("stranger"), None);
+    }
+    #[test] // this test leaves a 200 admin roster and checks only the sender's entry goes, and a non-admin leave is a no-op
+    fn leave_large_roster() {
+        let mut deps = mock_dependencies();
+
+        let admins: Vec<_> = (0..200).map(|i| format!("admin{i:03}")).collect();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: admins.clone(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let resp = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("stranger", &[]),
+            ExecuteMsg::Leave {},
+        )
+        .unwrap();
+        assert_eq!(resp, Response::new());
+        assert_eq!(ADMIN_COUNT.load(&deps.storage).unwrap(), 200);
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin100", &[]),
+            ExecuteMsg::Leave {},
+        )
+        .unwrap();
+
+        assert_eq!(ADMIN_COUNT.load(&deps.storage).unwrap(), 199);
+        for admin in &admins {
+            let admin = Addr::unchecked(admin);
+            assert_eq!(ADMINS.has(&deps.storage, &admin), admin != "admin100");
+        }
+    }
+    #[test] // this test lets the last admin leave and checks the fallback admin takes over as the sole admin
+    fn fallback_admin() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    fallback_admin: Some("fallback".to_owned()),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::Leave {},
+                &[],
+            )
+            .unwrap();
+        let wasm = resp.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+        assert!(wasm
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "fallback_activated" && attr.value == "fallback"));
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.admins, vec![Addr::unchecked("fallback")]);
+    }
+    #[test] // this test checks a moderator can't remove anyone and a super can remove everyone but the owner
+    fn removable_by_query() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["owner".to_owned(), "super1".to_owned()],
+                default_role: Some(Role::Moderator),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("super1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["mod1".to_owned()],
+            },
+        )
+        .unwrap();
+
+        let removable_by = |sender: &str| -> Vec<Addr> {
+            let resp: RemovableByResp = from_binary(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::RemovableBy {
+                        sender: sender.to_owned(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            resp.admins
+        };
+        assert_eq!(removable_by("mod1"), Vec::<Addr>::new());
+        assert_eq!(
+            removable_by("super1"),
+            vec![Addr::unchecked("mod1"), Addr::unchecked("super1")]
+        );
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("super1", &[]),
+            ExecuteMsg::RemoveMembers {
+                admins: vec!["owner".to_owned()],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("super1")
+            }
+        );
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mod1", &[]),
+            ExecuteMsg::RemoveMembers {
+                admins: vec!["super1".to_owned()],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("mod1")
+            }
+        );
+    }
+    #[test] // this test sets an over-length label through instantiate, SetLabel and SetMyLabel and expects the same error
+    fn label_length_limit() {
+        let mut deps = mock_dependencies();
+        let too_long = "x".repeat(9);
+
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                max_label_len: Some(8),
+                labels: vec![("admin1".to_owned(), too_long.clone())],
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::LabelTooLong { max: 8 });
+
+        let mut deps = mock_dependencies(); // the failed instantiate would have been reverted on chain
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                max_label_len: Some(8),
+                labels: vec![("admin1".to_owned(), "ops".to_owned())],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            LABELS.load(&deps.storage, &Addr::unchecked("admin1")).unwrap(),
+            "ops"
+        );
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SetLabel {
+                addr: "admin1".to_owned(),
+                label: too_long.clone(),
+                expected_version: 0,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::LabelTooLong { max: 8 });
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::SetMyLabel {
+                label: too_long,
+                expected_version: 0,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::LabelTooLong { max: 8 });
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::SetMyLabel {
+                label: "12345678".to_owned(),
+                expected_version: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            LABELS.load(&deps.storage, &Addr::unchecked("admin1")).unwrap(),
+            "12345678"
+        );
+    }
+    #[test] // this test checks BlockInfo echoes the app's current block
+    fn block_info_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg::default(),
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.update_block(|block| {
+            block.height += 7;
+            block.time = block.time.plus_seconds(35);
+        });
+
+        let resp: BlockInfoResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::BlockInfo {})
+            .unwrap();
+        let block = app.block_info();
+        assert_eq!(
+            resp,
+            BlockInfoResp {
+                height: block.height,
+                time: block.time,
+                chain_id: block.chain_id,
+            }
+        );
+    }
+    #[test] // this test has the owner remove a super admin a moderator couldn't, and keeps the last admin
+    fn owner_remove() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["super1".to_owned()],
+                default_role: Some(Role::Moderator),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("super1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["mod1".to_owned()],
+            },
+        )
+        .unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mod1", &[]),
+            ExecuteMsg::RemoveMembers {
+                admins: vec!["super1".to_owned()],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("mod1")
+            }
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::OwnerRemove {
+                addr: "super1".to_owned(),
+            },
+        )
+        .unwrap();
+        assert!(!ADMINS.has(&deps.storage, &Addr::unchecked("super1")));
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::OwnerRemove {
+                addr: "mod1".to_owned(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::LastAdmin {});
+    }
+    #[test] // this test checks the notifier gets a welcome message for every admin added
+    fn notify_on_join() {
+        let mut app = App::default();
+
+        let notifier_code =
+            ContractWrapper::new(notifier::execute, notifier::instantiate, notifier::query);
+        let notifier_code_id = app.store_code(Box::new(notifier_code));
+        let notifier_addr = app
+            .instantiate_contract(
+                notifier_code_id,
+                Addr::unchecked("owner"),
+                &Empty {},
+                &[],
+                "Notifier",
+                None,
+            )
+            .unwrap();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    notify_on_join: Some(notifier_addr.to_string()),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr,
+            &ExecuteMsg::AddMembers {
+                admins: vec!["admin2".to_owned(), "admin3".to_owned(), "admin1".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let welcomes: Vec<NotifierExecuteMsg> = app
+            .wrap()
+            .query_wasm_smart(notifier_addr, &Empty {})
+            .unwrap();
+        assert_eq!(
+            welcomes,
+            ["admin1", "admin2", "admin3"]
+                .into_iter()
+                .map(|admin| NotifierExecuteMsg::Welcome {
+                    admin: Addr::unchecked(admin),
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+    #[test] // this test passes the deployed config with one field changed and checks only that field is reported
+    fn config_matches_query() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                transfer_delay: 60,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let config_matches = |expected: Config| -> Vec<String> {
+            let resp: ConfigMatchesResp = from_binary(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::ConfigMatches {
+                        expected: Box::new(expected),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            resp.differing
+        };
+
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config_matches(config.clone()), Vec::<String>::new());
+        assert_eq!(
+            config_matches(Config {
+                transfer_delay: 120,
+                ..config
+            }),
+            vec!["transfer_delay".to_owned()]
+        );
+    }
+    #[test] // this test swaps a moderator's and a super's roles, and rejects a swap with a non-admin
+    fn swap_roles() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["super1".to_owned()],
+                default_role: Some(Role::Moderator),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("super1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["mod1".to_owned()],
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SwapRoles {
+                a: "mod1".to_owned(),
+                b: "super1".to_owned(),
+            },
+        )
+        .unwrap();
+
+        let admin = |addr: &str| ADMINS.load(&deps.storage, &Addr::unchecked(addr)).unwrap();
+        assert_eq!(admin("mod1").role, Role::Super);
+        assert_eq!(admin("super1").role, Role::Moderator);
+        assert_eq!(admin("mod1").version, 1);
+        assert_eq!(admin("super1").version, 1);
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SwapRoles {
+                a: "mod1".to_owned(),
+                b: "stranger".to_owned(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotAdmin {
+                addr: Addr::unchecked("stranger")
+            }
+        );
+    }
+    #[test] // this test instantiates with funds and two admins and checks the split and the leftover
+    fn instantiate_with_funds() {
+        let mut app = App::new(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("owner"), coins(201, "atom"))
+                .unwrap();
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                    ..Default::default()
+                },
+                &coins(101, "atom"),
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let balance = |who: &str| app.wrap().query_balance(who, "atom").unwrap().amount;
+        assert_eq!(balance("admin1"), Uint128::new(50));
+        assert_eq!(balance("admin2"), Uint128::new(50));
+        assert_eq!(balance(addr.as_str()), Uint128::new(1));
+
+        let empty = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg::default(),
+                &coins(100, "atom"),
+                "Empty",
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            app.wrap().query_balance(empty, "atom").unwrap().amount,
+            Uint128::new(100)
+        );
+    }
+    #[test] // this test mixes admin weights and checks only the heavy ones are listed, page by page
+    fn admins_by_min_weight_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        for (code, redeemer, weight) in [
+            ("code1", "heavy1", 5),
+            ("code2", "light", 2),
+            ("code3", "heavy2", 3),
+        ] {
+            app.execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::CreateInvite {
+                    code_hash: Binary::from(Sha256::digest(code.as_bytes()).to_vec()),
+                    role: Role::Super,
+                    weight,
+                    expires_at: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+            app.execute_contract(
+                Addr::unchecked(redeemer),
+                addr.clone(),
+                &ExecuteMsg::RedeemInvite {
+                    code: code.to_owned(),
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
+        let page = |start_after: Option<&str>| -> AdminsByMinWeightResp {
+            app.wrap()
+                .query_wasm_smart(
+                    addr.clone(),
+                    &QueryMsg::AdminsByMinWeight {
+                        min: 3,
+                        start_after: start_after.map(str::to_owned),
+                        limit: Some(1),
+                    },
+                )
+                .unwrap()
+        };
+
+        assert_eq!(page(None).admins, vec![(Addr::unchecked("heavy1"), 5)]);
+        assert_eq!(
+            page(Some("heavy1")).admins,
+            vec![(Addr::unchecked("heavy2"), 3)]
+        );
+        assert_eq!(page(Some("heavy2")).admins, vec![]);
+    }
+    #[test] // this test leaves one admin idle while the other acts, then prunes the idle one
+    fn prune_inactive_admins() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(100);
+        });
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::SetMyLabel {
+                label: "busy".to_owned(),
+                expected_version: 0,
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(100);
+        });
+
+        let resp: InactiveSinceResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::InactiveSince { duration: 150 })
+            .unwrap();
+        assert_eq!(resp.admins, vec![Addr::unchecked("admin2")]);
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::PruneInactive { duration: 150 },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotOwner {
+                sender: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::PruneInactive { duration: 150 },
+            &[],
+        )
+        .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.admins, vec![Addr::unchecked("admin1")]);
+    }
+    #[test] // this test sweeps malformed denoms and checks they are rejected before any query
+    fn sweep_invalid_denom() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+
+        for denom in ["1atom", "at", "atom!"] {
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                ExecuteMsg::Sweep {
+                    denom: denom.to_owned(),
+                    to: "treasury".to_owned(),
+                },
+            )
+            .unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidDenom {
+                    denom: denom.to_owned()
+                }
+            );
+        }
+    }
+    #[test] // this test checks the contract reports the address multi-test instantiated it at
+    fn self_address_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg::default(),
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp: SelfAddressResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::SelfAddress {})
+            .unwrap();
+        assert_eq!(resp.address, addr);
+    }
+    #[test] // this test sets a removal threshold of 2 and checks the admin is only removed on the second approval
+    fn removal_needs_two_approvals() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        "admin1".to_owned(),
+                        "admin2".to_owned(),
+                        "admin3".to_owned(),
+                    ],
+                    removal_threshold: Some(2),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::RemoveMembers {
+                    admins: vec!["admin3".to_owned()],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::ApprovalRequired {}, err.downcast().unwrap());
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::ProposeRemove {
+                addr: "admin3".to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: PendingRemovalsResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::PendingRemovals {})
+            .unwrap();
+        assert_eq!(
+            resp.removals,
+            vec![(Addr::unchecked("admin3"), vec![Addr::unchecked("admin1")])]
+        );
+
+        let approve = ExecuteMsg::ApproveRemove {
+            addr: "admin3".to_owned(),
+        };
+        let err = app
+            .execute_contract(Addr::unchecked("admin1"), addr.clone(), &approve, &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::AlreadyApproved {
+                sender: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(Addr::unchecked("admin2"), addr.clone(), &approve, &[])
+            .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")]
+        );
+
+        let resp: PendingRemovalsResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::PendingRemovals {})
+            .unwrap();
+        assert_eq!(resp.removals, vec![]);
+    }
+    #[test] // this test ranks three admins, two of them tied, and checks the ranked order
+    fn admins_by_rank_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        "admin1".to_owned(),
+                        "admin2".to_owned(),
+                        "admin3".to_owned(),
+                    ],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        for (admin, rank) in [("admin1", 2), ("admin2", 1), ("admin3", 1)] {
+            app.execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::SetRank {
+                    addr: admin.to_owned(),
+                    rank,
+                    expected_version: 0,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
+        let resp: AdminsByRankResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::AdminsByRank {})
+            .unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![
+                (Addr::unchecked("admin2"), 1),
+                (Addr::unchecked("admin3"), 1),
+                (Addr::unchecked("admin1"), 2),
+            ]
+        );
+    }
+    #[test] // this test checks the owner can't remove themself when they have to stay admin, and a new owner is added
+    fn owner_must_stay_admin() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    owner_must_be_admin: true,
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::RemoveMembers {
+                    admins: vec!["owner".to_owned()],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::OwnerMustBeAdmin {}, err.downcast().unwrap());
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::TransferOwnership {
+                new_owner: "owner2".to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked("owner2"),
+            addr.clone(),
+            &ExecuteMsg::AcceptOwnership {},
+            &[],
+        )
+        .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![
+                Addr::unchecked("owner"),
+                Addr::unchecked("admin1"),
+                Addr::unchecked("owner2"),
+            ]
+        );
+    }
+    #[test] // this test adds, removes and leaves, checking the exact count agrees with the cached one after each
+    fn count_exact_matches_count() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let ops = [
+            (
+                "admin1",
+                ExecuteMsg::AddMembers {
+                    admins: vec!["admin3".to_owned(), "admin4".to_owned()],
+                },
+            ),
+            (
+                "admin1",
+                ExecuteMsg::RemoveMembers {
+                    admins: vec!["admin2".to_owned()],
+                },
+            ),
+            ("admin3", ExecuteMsg::Leave {}),
+        ];
+        for (expected, (sender, msg)) in [4, 3, 2].into_iter().zip(ops) {
+            app.execute_contract(Addr::unchecked(sender), addr.clone(), &msg, &[])
+                .unwrap();
+
+            let count: CountResp = app
+                .wrap()
+                .query_wasm_smart(addr.clone(), &QueryMsg::Count {})
+                .unwrap();
+            let exact: CountResp = app
+                .wrap()
+                .query_wasm_smart(addr.clone(), &QueryMsg::CountExact {})
+                .unwrap();
+            assert_eq!(count, exact);
+            assert_eq!(exact.count, expected);
+        }
+    }
+    #[test] // this test lets moderators remove admins and checks a moderator's removal only works afterwards
+    fn moderator_remove_capability() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                    default_role: Some(Role::Moderator),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["mod1".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let remove = ExecuteMsg::RemoveMembers {
+            admins: vec!["admin2".to_owned()],
+        };
+        let err = app
+            .execute_contract(Addr::unchecked("mod1"), addr.clone(), &remove, &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("mod1")
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetRoleCapabilities {
+                role: Role::Moderator,
+                capabilities: Capabilities {
+                    can_remove: true,
+                    ..Role::Moderator.default_capabilities()
+                },
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(Addr::unchecked("mod1"), addr.clone(), &remove, &[])
+            .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![Addr::unchecked("mod1"), Addr::unchecked("admin1")]
+        );
+    }
+    #[test] // this test asks whether an admin and an outsider could set the greeting
+    fn can_execute_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let msg = ExecuteMsg::SetGreeting {
+            greeting: "gm".to_owned(),
+        };
+        let can_execute = |sender: &str| -> CanExecuteResp {
+            app.wrap()
+                .query_wasm_smart(
+                    addr.clone(),
+                    &QueryMsg::CanExecute {
+                        sender: sender.to_owned(),
+                        msg: msg.clone(),
+                    },
+                )
+                .unwrap()
+        };
+
+        assert_eq!(
+            can_execute("admin1"),
+            CanExecuteResp {
+                allowed: true,
+                reason: None,
+            }
+        );
+        assert_eq!(
+            can_execute("outsider"),
+            CanExecuteResp {
+                allowed: false,
+                reason: Some(
+                    ContractError::Unauthorized {
+                        sender: Addr::unchecked("outsider")
+                    }
+                    .to_string()
+                ),
+            }
+        );
+    }
+    #[test] // this test zeroes the admin counter behind the contract's back and checks a removal fails cleanly
+    fn count_underflow_on_desync() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        ADMIN_COUNT.save(&mut deps.storage, &0).unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::Leave {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::CountUnderflow {});
+        assert!(ADMINS.has(&deps.storage, &Addr::unchecked("admin1")));
+        assert_eq!(ADMIN_COUNT.load(&deps.storage).unwrap(), 0);
+    }
+    #[test] // this test blocks a denom and checks a donation containing it is rejected while others go through
+    fn donate_blocked_denom() {
+        let mut app = App::new(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked("donor"),
+                    vec![coin(100, "atom"), coin(100, "scam")],
+                )
+                .unwrap();
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    blocked_denoms: vec!["scam".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let donate = ExecuteMsg::DonateTo {
+            recipients: vec!["admin1".to_owned()],
+        };
+        let err = app
+            .execute_contract(
+                Addr::unchecked("donor"),
+                addr.clone(),
+                &donate,
+                &[coin(10, "atom"), coin(10, "scam")],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::DenomBlocked {
+                denom: "scam".to_owned()
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(Addr::unchecked("donor"), addr, &donate, &coins(10, "atom"))
+            .unwrap();
+        assert_eq!(
+            app.wrap().query_balance("admin1", "atom").unwrap().amount,
+            Uint128::new(10)
+        );
+        assert_eq!(
+            app.wrap().query_balance("admin1", "scam").unwrap().amount,
+            Uint128::zero()
+        );
+    }
+    #[test] // this test donates twice and checks the total keeps adding up after the payouts
+    fn total_donated_query() {
+        let mut app = App::new(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("donor"), coins(100, "atom"))
+                .unwrap();
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let donate = ExecuteMsg::DonateTo {
+            recipients: vec!["admin1".to_owned()],
+        };
+        for amount in [30, 12] {
+            app.execute_contract(
+                Addr::unchecked("donor"),
+                addr.clone(),
+                &donate,
+                &coins(amount, "atom"),
+            )
+            .unwrap();
+        }
+
+        let resp: TotalDonatedResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::TotalDonated {})
+            .unwrap();
+        assert_eq!(resp.total, coins(42, "atom"));
+    }
+    #[test] // this test sets a rate guard, changes the roster too quickly and checks the contract pauses itself
+    fn rate_guard_auto_pause() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetRateGuard {
+                max_changes: 2,
+                window: 60,
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["admin2".to_owned(), "admin3".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(30);
+        });
+
+        // the third change within a minute goes through but pauses everything after it
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("admin3"),
+                addr.clone(),
+                &ExecuteMsg::Leave {},
+                &[],
+            )
+            .unwrap();
+        let wasm = resp.events.iter().find(|event| event.ty == "wasm").unwrap();
+        assert!(wasm.attributes.iter().any(|attr| attr.key == "auto_paused"));
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::SetMyLabel {
+                    label: "ops".to_owned(),
+                    expected_version: 0,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Maintenance {
+                until: PAUSED_INDEFINITELY
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr,
+            &ExecuteMsg::SetMaintenance { until: None },
+            &[],
+        )
+        .unwrap();
+    }
+    #[test] // this test runs the env-independent queries at two block heights and checks the answers are identical
+    fn env_independent_queries() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut later = mock_env();
+        later.block.height += 1_000;
+        later.block.time = later.block.time.plus_seconds(5_000);
+        for msg in [
+            QueryMsg::Greet {},
+            QueryMsg::AdminsList {
+                start_after: None,
+                limit: None,
+            },
+        ] {
+            assert_eq!(
+                query(deps.as_ref(), mock_env(), msg.clone()).unwrap(),
+                query(deps.as_ref(), later.clone(), msg).unwrap()
+            );
+        }
+    }
+    #[test] // this test lets one admin expire, migrates with pruning and checks only that admin is gone
+    fn migrate_prunes_expired() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let expired = Addr::unchecked("admin1");
+        ADMINS
+            .update(&mut deps.storage, &expired, |admin_info| -> StdResult<_> {
+                Ok(AdminInfo {
+                    expires_at: env.block.height,
+                    ..admin_info.unwrap()
+                })
+            })
+            .unwrap();
+
+        let resp = migrate(
+            deps.as_mut(),
+            env,
+            MigrateMsg {
+                prune_expired: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(resp.attributes, vec![("pruned", "1")]);
+        assert!(!ADMINS.has(&deps.storage, &expired));
+        assert!(ADMINS.has(&deps.storage, &Addr::unchecked("admin2")));
+        assert_eq!(ADMIN_COUNT.load(&deps.storage).unwrap(), 1);
+    }
+    #[test] // this test delegates an admin's rights, lets the delegate add someone and checks it stops once expired
+    fn delegate_add_rights() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let until = app.block_info().time.plus_seconds(100);
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Delegate {
+                to: "deputy".to_owned(),
+                until,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: DelegationsResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::Delegations {})
+            .unwrap();
+        assert_eq!(
+            resp.delegations,
+            vec![(Addr::unchecked("admin1"), Addr::unchecked("deputy"), until)]
+        );
+
+        app.execute_contract(
+            Addr::unchecked("deputy"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec!["admin2".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(100);
+        });
+        let err = app
+            .execute_contract(
+                Addr::unchecked("deputy"),
+                addr.clone(),
+                &ExecuteMsg::AddMembers {
+                    admins: vec!["admin3".to_owned()],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("deputy")
+            },
+            err.downcast().unwrap()
+        );
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")]
+        );
+    }
+    #[test] // this test checks EffectiveAuthority for an admin, their delegate and an unrelated address
+    fn effective_authority_of_delegate() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Delegate {
+                to: "deputy".to_owned(),
+                until: app.block_info().time.plus_seconds(100),
+            },
+            &[],
+        )
+        .unwrap();
+
+        for (sender, authority) in [
+            ("admin1", Some(Addr::unchecked("admin1"))),
+            ("deputy", Some(Addr::unchecked("admin1"))),
+            ("stranger", None),
+        ] {
+            let resp: EffectiveAuthorityResp = app
+                .wrap()
+                .query_wasm_smart(
+                    addr.clone(),
+                    &QueryMsg::EffectiveAuthority {
+                        sender: sender.to_owned(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(resp.authority, authority);
+        }
+    }
+    #[test] // this test instantiates with an expected sender, once sent by it and once by someone else
+    fn instantiate_expected_sender() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let msg = InstantiateMsg {
+            admins: vec!["admin1".to_owned()],
+            expected_sender: Some("owner".to_owned()),
+            ..Default::default()
+        };
+
+        let err = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("frontrunner"),
+                &msg,
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::UnexpectedSender {
+                expected: Addr::unchecked("owner"),
+                sender: Addr::unchecked("frontrunner")
+            },
+            err.downcast().unwrap()
+        );
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &msg,
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.admins, vec![Addr::unchecked("admin1")]);
+    }
+    #[test] // this test exports a small roster in two pages and checks the snapshot fields
+    fn export_state() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                    labels: vec![("admin2".to_owned(), "ops".to_owned())],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp: StateExport = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::Export {
+                    start_after: None,
+                    limit: Some(1),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.owner, Addr::unchecked("owner"));
+        assert_eq!(resp.greeting, "Hello World");
+        assert_eq!(resp.admin_count, 2);
+        assert_eq!(resp.roster_changes, 2);
+        assert!(!resp.config.greeting_locked);
+        assert_eq!(resp.admins.len(), 1);
+        assert_eq!(resp.admins[0].addr, "admin1");
+        assert_eq!(resp.admins[0].added_by, "owner");
+
+        let resp: StateExport = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::Export {
+                    start_after: Some("admin1".to_owned()),
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.admins.len(), 1);
+        assert_eq!(resp.admins[0].addr, "admin2");
+        assert_eq!(resp.admins[0].label, Some("ops".to_owned()));
+    }
+    #[test] // this test puts the contract in read-only mode through sudo and checks even the owner is rejected
+    fn read_only_through_sudo() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query).with_sudo(sudo);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.wasm_sudo(addr.clone(), &SudoMsg::SetReadOnly { read_only: true })
+            .unwrap();
+
+        let owner_msg = ExecuteMsg::TransferOwnership {
+            new_owner: "owner2".to_owned(),
+        };
+        let admin_msg = ExecuteMsg::SetGreeting {
+            greeting: "Hi".to_owned(),
+        };
+        for (sender, msg) in [("owner", &owner_msg), ("admin1", &admin_msg)] {
+            let err = app
+                .execute_contract(Addr::unchecked(sender), addr.clone(), msg, &[])
+                .unwrap_err();
+            assert_eq!(ContractError::ReadOnly {}, err.downcast().unwrap());
+        }
+
+        let resp: GreetResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::Greet {})
+            .unwrap();
+        assert_eq!(resp.message, "Hello World");
+
+        app.wasm_sudo(addr.clone(), &SudoMsg::SetReadOnly { read_only: false })
+            .unwrap();
+        app.execute_contract(Addr::unchecked("admin1"), addr, &admin_msg, &[])
+            .unwrap();
+    }
+    #[test] // this test sends two label updates based on the same version and checks the second is rejected
+    fn stale_update_rejected() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SetLabel {
+                addr: "admin1".to_owned(),
+                label: "ops".to_owned(),
+                expected_version: 0,
+            },
+        )
+        .unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::SetMyLabel {
+                label: "infra".to_owned(),
+                expected_version: 0,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::StaleUpdate {
+                expected: 0,
+                actual: 1
+            }
+        );
+        assert_eq!(
+            LABELS
+                .load(&deps.storage, &Addr::unchecked("admin1"))
+                .unwrap(),
+            "ops"
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::SetMyLabel {
+                label: "infra".to_owned(),
+                expected_version: 1,
+            },
+        )
+        .unwrap();
+        let admin_info = ADMINS
+            .load(&deps.storage, &Addr::unchecked("admin1"))
+            .unwrap();
+        assert_eq!(admin_info.version, 2);
+    }
+    #[test] // this test labels three admins in one go, skipping an address that isn't an admin
+    fn set_labels_in_bulk() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        "admin1".to_owned(),
+                        "admin2".to_owned(),
+                        "admin3".to_owned(),
+                    ],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let entries = [
+            ("admin1", "ops"),
+            ("admin2", "infra"),
+            ("stranger", "nobody"),
+            ("admin3", "treasury"),
+        ];
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::SetLabels {
+                entries: entries
+                    .iter()
+                    .map(|(admin, label)| (admin.to_string(), label.to_string()))
+                    .collect(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: StateExport = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::Export {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        let labels: Vec<_> = resp
+            .admins
+            .into_iter()
+            .map(|record| (record.addr, record.label))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("admin1".to_owned(), Some("ops".to_owned())),
+                ("admin2".to_owned(), Some("infra".to_owned())),
+                ("admin3".to_owned(), Some("treasury".to_owned())),
+            ]
+        );
+    }
+    #[test] // this test intersects a mixed list with duplicates against the roster and checks the size cap
+    fn intersection_query() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec![
+                    "admin1".to_owned(),
+                    "admin2".to_owned(),
+                    "admin3".to_owned(),
+                ],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let addrs = ["stranger", "admin3", "admin1", "admin3", "owner"]
+            .map(|addr| addr.to_owned())
+            .to_vec();
+        let resp = query::intersection(deps.as_ref(), addrs).unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![Addr::unchecked("admin1"), Addr::unchecked("admin3")]
+        );
+
+        let too_many = vec!["admin1".to_owned(); 101];
+        query::intersection(deps.as_ref(), too_many).unwrap_err();
+    }
+    #[test] // this test redeems weighted invites up to the weight cap and checks one more is rejected
+    fn max_total_weight_cap() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    max_total_weight: Some(5),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        for (code, weight) in [("first", 4), ("second", 1)] {
+            app.execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::CreateInvite {
+                    code_hash: Binary::from(Sha256::digest(code.as_bytes()).to_vec()),
+                    role: Role::Moderator,
+                    weight,
+                    expires_at: None,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
+        // 1 + 4 reaches the cap exactly
+        app.execute_contract(
+            Addr::unchecked("newbie1"),
+            addr.clone(),
+            &ExecuteMsg::RedeemInvite {
+                code: "first".to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("newbie2"),
+                addr.clone(),
+                &ExecuteMsg::RedeemInvite {
+                    code: "second".to_owned(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::WeightCapExceeded { max: 5 },
+            err.downcast().unwrap()
+        );
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![Addr::unchecked("admin1"), Addr::unchecked("newbie1")]
+        );
+    }
+    #[test] // this test previews a donation with PayoutPlan and checks DonateTo pays out exactly that
+    fn payout_plan_matches_donation() {
+        let mut app = App::new(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("donor"), coins(101, "atom"))
+                .unwrap();
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        "admin1".to_owned(),
+                        "admin2".to_owned(),
+                        "admin3".to_owned(),
+                    ],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let plan: PayoutPlanResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::PayoutPlan {
+                    funds: coins(101, "atom"),
+                },
+            )
+            .unwrap();
+        assert_eq!(plan.leftover, coins(2, "atom"));
+        assert_eq!(plan.payouts.len(), 3);
+
+        app.execute_contract(
+            Addr::unchecked("donor"),
+            addr.clone(),
+            &ExecuteMsg::DonateTo {
+                recipients: vec![
+                    "admin1".to_owned(),
+                    "admin2".to_owned(),
+                    "admin3".to_owned(),
+                ],
+            },
+            &coins(101, "atom"),
+        )
+        .unwrap();
+
+        for (admin, payout) in plan.payouts {
+            let balance = app.wrap().query_all_balances(admin).unwrap();
+            assert_eq!(balance, payout);
+        }
+        let balance = app.wrap().query_all_balances(addr).unwrap();
+        assert_eq!(balance, plan.leftover);
+    }
+    #[test] // this test donates nothing and only zero amounts and checks both are rejected
+    fn empty_donation_rejected() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        for funds in [vec![], vec![coin(0, "atom"), coin(0, "uosmo")]] {
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("donor", &funds),
+                ExecuteMsg::DonateTo {
+                    recipients: vec!["admin1".to_owned()],
+                },
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::EmptyDonation {});
+        }
+    }
+    #[test] // this test proposes a freeze, acts during the grace period and checks execs are blocked once it passes
+    fn freeze_after_grace_period() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    freeze_delay: 100,
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::ProposeFreeze {},
+            &[],
+        )
+        .unwrap();
+        let freeze_at = app.block_info().time.plus_seconds(100);
+
+        let status: FreezeStatusResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::FreezeStatus {})
+            .unwrap();
+        assert_eq!(
+            status,
+            FreezeStatusResp {
+                frozen: false,
+                freeze_at: Some(freeze_at),
+            }
+        );
+
+        let add = |admin: &str| ExecuteMsg::AddMembers {
+            admins: vec![admin.to_owned()],
+        };
+        app.execute_contract(Addr::unchecked("admin1"), addr.clone(), &add("admin2"), &[])
+            .unwrap();
+
+        app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(100);
+        });
+        let err = app
+            .execute_contract(Addr::unchecked("admin1"), addr.clone(), &add("admin3"), &[])
+            .unwrap_err();
+        assert_eq!(ContractError::Frozen {}, err.downcast().unwrap());
+
+        let status: FreezeStatusResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::FreezeStatus {})
+            .unwrap();
+        assert!(status.frozen);
+    }
+    #[test] // this test merges a second instance's roster into the first and checks shared admins aren't doubled
+    fn merge_from_other_contract() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let mut instantiate_with = |admins: &[&str]| {
+            app.instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: admins.iter().map(|admin| admin.to_string()).collect(),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap()
+        };
+        let addr = instantiate_with(&["admin1", "admin2"]);
+        let other = instantiate_with(&["admin2", "admin3"]);
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::MergeFrom {
+                other: other.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: CountResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::CountExact {})
+            .unwrap();
+        assert_eq!(resp.count, 3);
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![
+                Addr::unchecked("admin1"),
+                Addr::unchecked("admin2"),
+                Addr::unchecked("admin3"),
+            ]
+        );
+    }
+    #[test] // this test compares two instances with overlapping rosters in both directions
+    fn diff_from_other_contract() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let mut instantiate_with = |admins: &[&str]| {
+            app.instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: admins.iter().map(|admin| admin.to_string()).collect(),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap()
+        };
+        let addr = instantiate_with(&["admin1", "admin2", "admin3"]);
+        let other = instantiate_with(&["admin2", "admin4"]);
+
+        let diff = |from: &Addr, to: &Addr| -> DiffFromResp {
+            app.wrap()
+                .query_wasm_smart(
+                    from,
+                    &QueryMsg::DiffFrom {
+                        other: to.to_string(),
+                    },
+                )
+                .unwrap()
+        };
+        assert_eq!(
+            diff(&addr, &other).admins,
+            vec![Addr::unchecked("admin1"), Addr::unchecked("admin3")]
+        );
+        assert_eq!(diff(&other, &addr).admins, vec![Addr::unchecked("admin4")]);
+    }
+    #[test] // this test checks an admin delegating to themself is rejected and nothing is stored
+    fn delegate_to_self_rejected() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::Delegate {
+                to: "admin1".to_owned(),
+                until: mock_env().block.time.plus_seconds(100),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::CannotDelegateToSelf {});
+        assert!(!DELEGATIONS.has(&deps.storage, &Addr::unchecked("admin1")));
+    }
+    #[test] // this test delegates, fails to delegate again, revokes and then delegates to someone else
+    fn delegate_revoke_and_redelegate() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let until = mock_env().block.time.plus_seconds(100);
+        let delegate = |to: &str| ExecuteMsg::Delegate {
+            to: to.to_owned(),
+            until,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            delegate("deputy1"),
+        )
+        .unwrap();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            delegate("deputy2"),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::DelegationExists {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::RevokeDelegation {},
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            delegate("deputy2"),
+        )
+        .unwrap();
+        assert_eq!(
+            DELEGATIONS
+                .load(&deps.storage, &Addr::unchecked("admin1"))
+                .unwrap(),
+            (Addr::unchecked("deputy2"), until)
+        );
+    }
+    #[test] // this test diffs a config with two changed fields and checks UpdateConfig emits config_changed
+    fn config_changed_event_fields() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(config_changed_event(&config, &config), None);
+        let changed = Config {
+            transfer_delay: 60,
+            freeze_delay: 30,
+            ..config.clone()
+        };
+        let event = config_changed_event(&config, &changed).unwrap();
+        assert_eq!(event.ty, "config_changed");
+        assert_eq!(
+            event.attributes,
+            vec![
+                Attribute::new("field", "transfer_delay"),
+                Attribute::new("field", "freeze_delay"),
+            ]
+        );
+
+        let resp = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateConfig {
+                default_role: Some(Role::Moderator),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            resp.events,
+            vec![Event::new("config_changed").add_attribute("field", "default_role")]
+        );
+    }
+    #[test] // this test imports active and expired admins, proposes one more and checks the status counts
+    fn status_counts_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![],
+                    migration_source: Some("old".to_owned()),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let height = app.block_info().height;
+        let record = |addr: &str, expires_at: u64| AdminRecord {
+            addr: addr.to_owned(),
+            joined: Timestamp::from_seconds(0),
+            role: Role::Super,
+            weight: 1,
+            expires_at,
+            added_by: "owner".to_owned(),
+            label: None,
+            annotations: vec![],
+            rank: 0,
+            version: 0,
+            joined_height: 0,
+        };
+        app.execute_contract(
+            Addr::unchecked("old"),
+            addr.clone(),
+            &ExecuteMsg::ImportRoster {
+                admins: vec![
+                    record("admin1", 0),
+                    record("admin2", height + 10),
+                    record("admin3", height),
+                ],
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::ProposeAdmin {
+                addr: "newbie".to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: StatusCountsResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::StatusCounts {})
+            .unwrap();
+        assert_eq!(
+            resp,
+            StatusCountsResp {
+                active: 2,
+                expired: 1,
+                pending: 1,
+            }
+        );
+    }
+    #[test] // this test seeds a wrong admin count on a pre-version-1 deployment and checks migrate recounts it
+    fn migrate_recounts_admins() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        ADMIN_COUNT.save(&mut deps.storage, &5).unwrap();
+        STATE_VERSION.remove(&mut deps.storage); // as stored by deployments from before versioning
+
+        let resp = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+        assert_eq!(resp.attributes, vec![("admin_count", "2")]);
+        assert_eq!(query::count(deps.as_ref()).unwrap().count, 2);
+        assert_eq!(STATE_VERSION.load(&deps.storage).unwrap(), 1);
+
+        // already on version 1, a later migration leaves the count alone
+        ADMIN_COUNT.save(&mut deps.storage, &5).unwrap();
+        let resp = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap();
+        assert!(resp.attributes.is_empty());
+        assert_eq!(query::count(deps.as_ref()).unwrap().count, 5);
+    }
+    #[test] // this test checks an empty roster is only reported bricked when no fallback admin is configured
+    fn is_bricked_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        for (admins, fallback_admin, bricked) in [
+            (vec!["admin1".to_owned()], None, false),
+            (vec![], Some("rescuer".to_owned()), false),
+            (vec![], None, true),
+        ] {
+            let addr = app
+                .instantiate_contract(
+                    code_id,
+                    Addr::unchecked("owner"),
+                    &InstantiateMsg {
+                        admins,
+                        fallback_admin,
+                        ..Default::default()
+                    },
+                    &[],
+                    "Contract",
+                    None,
+                )
+                .unwrap();
+
+            let resp: IsBrickedResp = app
+                .wrap()
+                .query_wasm_smart(addr.clone(), &QueryMsg::IsBricked {})
+                .unwrap();
+            assert_eq!(resp.bricked, bricked);
+            let resp: CountResp = app
+                .wrap()
+                .query_wasm_smart(addr, &QueryMsg::Count {})
+                .unwrap();
+            assert_eq!(resp.count == 0, bricked); // the fallback admin steps in right away
+        }
+    }
+    #[test] // this test starts from an empty roster, checks it is bricked and recovers it through sudo
+    fn recover_through_sudo() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query).with_sudo(sudo);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp: IsBrickedResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::IsBricked {})
+            .unwrap();
+        assert!(resp.bricked);
+
+        let recover = SudoMsg::Recover {
+            admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+        };
+        app.wasm_sudo(addr.clone(), &recover).unwrap();
+
+        let resp: IsBrickedResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::IsBricked {})
+            .unwrap();
+        assert!(!resp.bricked);
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")]
+        );
+
+        // only an empty roster can be recovered
+        let err = app.wasm_sudo(addr, &recover).unwrap_err();
+        assert_eq!(ContractError::AlreadyPopulated {}, err.downcast().unwrap());
+    }
+    #[test] // this test donates below and at the configured minimum and checks only the latter goes through
+    fn donate_min_donation() {
+        let mut deps = mock_dependencies();
+
+        // the minimum applies to the funds sent to instantiate as well
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &coins(9, "atom")),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                min_donations: vec![("atom".to_owned(), Uint128::new(10))],
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DonationTooSmall {
+                denom: "atom".to_owned(),
+                min: Uint128::new(10)
+            }
+        );
+
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                min_donations: vec![("atom".to_owned(), Uint128::new(10))],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let donate = ExecuteMsg::DonateTo {
+            recipients: vec!["admin1".to_owned()],
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("donor", &coins(9, "atom")),
+            donate.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DonationTooSmall {
+                denom: "atom".to_owned(),
+                min: Uint128::new(10)
+            }
+        );
+
+        // the minimum itself is accepted, as is any amount of a denom without one
+        for funds in [coins(10, "atom"), coins(1, "uosmo")] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("donor", &funds),
+                donate.clone(),
+            )
+            .unwrap();
+        }
+        assert_eq!(
+            TOTAL_DONATED.load(&deps.storage, "atom").unwrap(),
+            Uint128::new(10)
+        );
+    }
+    #[test] // this test adds admins at three heights and queries the admins added within a sub-range
+    fn admins_added_between_heights() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+        let start = app.block_info().height;
+
+        for admin in ["admin2", "admin3"] {
+            app.update_block(|block| {
+                block.height += 1;
+                block.time = block.time.plus_seconds(5);
+            });
+            app.execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::AddMembers {
+                    admins: vec![admin.to_owned()],
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
+        let added_between = |from_height: u64, to_height: u64| {
+            let resp: AdminsAddedBetweenResp = app
+                .wrap()
+                .query_wasm_smart(
+                    addr.clone(),
+                    &QueryMsg::AdminsAddedBetween {
+                        from_height,
+                        to_height,
+                    },
+                )
+                .unwrap();
+            resp.admins
+        };
+        assert_eq!(
+            added_between(start + 1, start + 1),
+            vec![Addr::unchecked("admin2")]
+        );
+        assert_eq!(
+            added_between(start + 1, start + 2),
+            vec![Addr::unchecked("admin2"), Addr::unchecked("admin3")]
+        );
+        assert_eq!(added_between(start, start).len(), 1);
+    }
+    #[test] // this test reorders three admins and checks AdminsByRank follows the new order
+    fn reorder_admins() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        "admin1".to_owned(),
+                        "admin2".to_owned(),
+                        "admin3".to_owned(),
+                    ],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::Reorder {
+                    order: vec!["admin1".to_owned(), "stranger".to_owned()],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotAdmin {
+                addr: Addr::unchecked("stranger")
+            },
+            err.downcast().unwrap()
+        );
+        let err = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::Reorder {
+                    order: vec![
+                        "admin1".to_owned(),
+                        "admin2".to_owned(),
+                        "admin1".to_owned(),
+                    ],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::DuplicateAddress {
+                addr: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::Reorder {
+                order: vec![
+                    "admin3".to_owned(),
+                    "admin1".to_owned(),
+                    "admin2".to_owned(),
+                ],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: AdminsByRankResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::AdminsByRank {})
+            .unwrap();
+        assert_eq!(
+            resp.admins,
+            vec![
+                (Addr::unchecked("admin3"), 0),
+                (Addr::unchecked("admin1"), 1),
+                (Addr::unchecked("admin2"), 2),
+            ]
+        );
+    }
+    #[test] // this test lets a proposal expire, checks it can't be accepted anymore and prunes it
+    fn proposal_ttl_expiry_and_prune() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec!["admin1".to_owned()],
+                    proposal_ttl: Some(100),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::ProposeAdmin {
+                addr: "newbie".to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(|block| block.time = block.time.plus_seconds(100));
+
+        let resp: PrunableProposalsResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::PrunableProposals {})
+            .unwrap();
+        assert_eq!(
+            resp,
+            PrunableProposalsResp {
+                additions: vec![Addr::unchecked("newbie")],
+                removals: vec![],
+            }
+        );
+        let resp: PendingSelfAcceptResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::PendingSelfAccept {})
+            .unwrap();
+        assert!(resp.pending.is_empty());
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("newbie"),
+                addr.clone(),
+                &ExecuteMsg::AcceptAdmin {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::ProposalExpired {
+                addr: Addr::unchecked("newbie")
+            },
+            err.downcast().unwrap()
+        );
+
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::PruneProposals {},
+                &[],
+            )
+            .unwrap();
+        let wasm = resp.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+        assert!(wasm
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "pruned" && attr.value == "1"));
+
+        let resp: PrunableProposalsResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::PrunableProposals {})
+            .unwrap();
+        assert!(resp.additions.is_empty());
+        let err = app
+            .execute_contract(
+                Addr::unchecked("newbie"),
+                addr,
+                &ExecuteMsg::AcceptAdmin {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotProposed {
+                sender: Addr::unchecked("newbie")
+            },
+            err.downcast().unwrap()
+        );
+    }
+    #[test] // this test checks the policy snapshot reflects the configured authorization settings
+    fn policy_snapshot() {
+        let mut deps = mock_dependencies();
+
+        let moderator_caps = Capabilities {
+            can_add: false,
+            can_remove: false,
+            can_pause: true,
+            can_config: false,
+        };
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                require_acceptance: true,
+                removal_threshold: Some(2),
+                per_admin_cooldown: Some(30),
+                role_capabilities: vec![(Role::Moderator, moderator_caps)],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SetMaintenance {
+                until: Some(mock_env().block.time.plus_seconds(60)),
+            },
+        )
+        .unwrap();
+
+        let policy: PolicyResp =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Policy {}).unwrap()).unwrap();
+        assert_eq!(policy.owner, Addr::unchecked("owner"));
+        assert!(policy.require_acceptance);
+        assert_eq!(policy.removal_threshold, Some(2));
+        assert_eq!(policy.per_admin_cooldown, Some(30));
+        assert_eq!(
+            policy.role_capabilities,
+            vec![
+                (Role::Super, Role::Super.default_capabilities()),
+                (Role::Moderator, moderator_caps),
+            ]
+        );
+        assert!(!policy.frozen);
+        assert!(!policy.read_only);
+        assert_eq!(
+            policy.paused_until,
+            Some(mock_env().block.time.plus_seconds(60))
+        );
+    }
+    #[test] // this test lets an imported admin expire, pokes and checks they're removed with an event
+    fn poke_expiry_removes_expired() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![],
+                    migration_source: Some("old".to_owned()),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let height = app.block_info().height;
+        let record = |addr: &str, expires_at: u64| AdminRecord {
+            addr: addr.to_owned(),
+            joined: Timestamp::from_seconds(0),
+            role: Role::Super,
+            weight: 1,
+            expires_at,
+            added_by: "owner".to_owned(),
+            label: None,
+            annotations: vec![],
+            rank: 0,
+            version: 0,
+            joined_height: 0,
+        };
+        app.execute_contract(
+            Addr::unchecked("old"),
+            addr.clone(),
+            &ExecuteMsg::ImportRoster {
+                admins: vec![record("admin1", 0), record("admin2", height + 5)],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("keeper"),
+                addr.clone(),
+                &ExecuteMsg::PokeExpiry {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::NothingExpired {}, err.downcast().unwrap());
+
+        app.update_block(|block| block.height += 5);
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("keeper"),
+                addr.clone(),
+                &ExecuteMsg::PokeExpiry {},
+                &[],
+            )
+            .unwrap();
+        let expired = resp
+            .events
+            .iter()
+            .find(|event| event.ty == "wasm-admin_expired")
+            .unwrap();
+        assert!(expired
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "addr" && attr.value == "admin2"));
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.admins, vec![Addr::unchecked("admin1")]);
+    }
+    #[test] // this test imports several expiring admins and checks the earliest active one is next
+    fn next_expiry_query() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![],
+                    migration_source: Some("old".to_owned()),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let next_expiry = |app: &App| {
+            let resp: NextExpiryResp = app
+                .wrap()
+                .query_wasm_smart(addr.clone(), &QueryMsg::NextExpiry {})
+                .unwrap();
+            resp.next
+        };
+        assert_eq!(next_expiry(&app), None);
+
+        let height = app.block_info().height;
+        let record = |addr: &str, expires_at: u64| AdminRecord {
+            addr: addr.to_owned(),
+            joined: Timestamp::from_seconds(0),
+            role: Role::Super,
+            weight: 1,
+            expires_at,
+            added_by: "owner".to_owned(),
+            label: None,
+            annotations: vec![],
+            rank: 0,
+            version: 0,
+            joined_height: 0,
+        };
+        app.execute_contract(
+            Addr::unchecked("old"),
+            addr.clone(),
+            &ExecuteMsg::ImportRoster {
+                admins: vec![
+                    record("admin1", 0),
+                    record("admin2", height + 20),
+                    record("admin3", height + 5),
+                    record("admin4", height),
+                ],
+            },
+            &[],
+        )
+        .unwrap();
+
+        // admin4 is already expired and admin1 never expires
+        assert_eq!(
+            next_expiry(&app),
+            Some((Addr::unchecked("admin3"), height + 5))
+        );
+        app.update_block(|block| block.height += 5);
+        assert_eq!(
+            next_expiry(&app),
+            Some((Addr::unchecked("admin2"), height + 20))
+        );
+    }
+    #[test] // this test rejects an instantiation with more initial admins than max_admins, and an add past it
+    fn instantiate_over_max_admins() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            admins: vec![
+                "admin1".to_owned(),
+                "admin2".to_owned(),
+                "admin3".to_owned(),
+            ],
+            max_admins: Some(2),
+            ..Default::default()
+        };
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TooManyAdmins { max: 2 });
+
+        // mock storage isn't rolled back on errors, so the rest runs on a fresh contract
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                ..msg
+            },
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["admin3".to_owned()],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TooManyAdmins { max: 2 });
+    }
+    #[test] // this test checks an address can join until the admin cap is reached
+    fn can_join_query() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                max_admins: Some(2),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let can_join = |deps: Deps, addr: &str| -> CanJoinResp {
+            let msg = QueryMsg::CanJoin {
+                addr: addr.to_owned(),
+            };
+            from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+        };
+        assert_eq!(
+            can_join(deps.as_ref(), "newbie"),
+            CanJoinResp {
+                can: true,
+                reasons: vec![],
+            }
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["newbie".to_owned()],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            can_join(deps.as_ref(), "latecomer"),
+            CanJoinResp {
+                can: false,
+                reasons: vec![ContractError::TooManyAdmins { max: 2 }.to_string()],
+            }
+        );
+    }
+    #[test] // this test checks the join time index follows additions and removals, ties going by canonical address
+    fn admins_ordered_index() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        let mut add = |addr: &str, env: &Env| {
+            let admin_info = AdminInfo::new(&env.block, Addr::unchecked("owner"));
+            add_admin(
+                &mut deps.storage,
+                &deps.api,
+                &Addr::unchecked(addr),
+                &admin_info,
+            )
+            .unwrap();
+        };
+        add("carol", &env);
+        env.block.time = env.block.time.plus_seconds(10);
+        add("bob", &env);
+        add("alice", &env);
+        add("dave", &env);
+
+        // a stale index entry would make admins_ordered fail to load the admin
+        remove_admin_fully(&mut deps.storage, &deps.api, &Addr::unchecked("alice")).unwrap();
+        let ordered: Vec<_> = admins_ordered(&deps.storage)
+            .unwrap()
+            .into_iter()
+            .map(|(addr, _)| addr)
+            .collect();
+        assert_eq!(
+            ordered,
+            vec![
+                Addr::unchecked("carol"),
+                Addr::unchecked("bob"),
+                Addr::unchecked("dave"),
+            ]
+        );
+
+        let page =
+            admins_ordered_page(&deps.storage, &deps.api, Some(&Addr::unchecked("carol")), 1)
+                .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].0, Addr::unchecked("bob"));
+    }
+    #[test] // this test pages through the admins list with start_after
+    fn admins_list_pagination() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec![
+                    "admin1".to_owned(),
+                    "admin2".to_owned(),
+                    "admin3".to_owned(),
+                ],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let page = |deps: Deps, start_after: Option<&str>| -> Vec<Addr> {
+            let msg = QueryMsg::AdminsList {
+                start_after: start_after.map(str::to_owned),
+                limit: Some(2),
+            };
+            let resp: AdminsListResp = from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+            resp.admins
+        };
+        assert_eq!(
+            page(deps.as_ref(), None),
+            vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")]
+        );
+        assert_eq!(
+            page(deps.as_ref(), Some("admin2")),
+            vec![Addr::unchecked("admin3")]
+        );
+        assert_eq!(page(deps.as_ref(), Some("admin3")), Vec::<Addr>::new());
+    }
+    #[test] // this test checks a moderator can't create an invite, which could grant the super role
+    fn moderator_cannot_create_invite() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["super1".to_owned()],
+                default_role: Some(Role::Moderator),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("super1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["mod1".to_owned()],
+            },
+        )
+        .unwrap();
+
+        let invite = |code: &str| ExecuteMsg::CreateInvite {
+            code_hash: Binary::from(Sha256::digest(code.as_bytes()).to_vec()),
+            role: Role::Super,
+            weight: 10,
+            expires_at: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mod1", &[]),
+            invite("mod"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("mod1")
+            }
+        );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("super1", &[]),
+            invite("super"),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            invite("owner"),
+        )
+        .unwrap();
+    }
+    #[test] // this test checks a moderator can't annotate admins while a super admin can
+    fn moderator_cannot_annotate() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["super1".to_owned()],
+                default_role: Some(Role::Moderator),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("super1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["mod1".to_owned()],
+            },
+        )
+        .unwrap();
+
+        let annotate = |expected_version: u64| ExecuteMsg::SetAnnotation {
+            addr: "super1".to_owned(),
+            key: "team".to_owned(),
+            value: "infra".to_owned(),
+            expected_version,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("mod1", &[]),
+            annotate(0),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("mod1")
+            }
+        );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("super1", &[]),
+            annotate(0),
+        )
+        .unwrap();
+    }
+    #[test]
+    fn migrate_converts_original_admins() {
+        let mut deps = mock_dependencies();
+        // the state the first version of the contract left behind
+        let legacy = vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")];
+        deps.storage.set(b"admins", &to_vec(&legacy).unwrap());
+
+        let resp = migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                owner: Some("admin1".to_owned()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(resp.attributes.contains(&Attribute::new("converted", "2")));
+        assert_eq!(deps.storage.get(b"admins"), None);
+
+        let resp: AdminsListResp = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(resp.admins, legacy);
+        let resp: CountResp =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Count {}).unwrap()).unwrap();
+        assert_eq!(resp, CountResp { count: 2 });
+        let resp: GreetResp =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Greet {}).unwrap()).unwrap();
+        assert_eq!(resp.message, "Hello World");
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["admin3".to_owned()],
+            },
+        )
+        .unwrap();
+        assert!(ADMINS.has(&deps.storage, &Addr::unchecked("admin3")));
+    }
+    #[test] // this test checks RemovableBy leaves out a required owner and is empty while removals need approvals
+    fn removable_by_follows_remove_members() {
+        let removable_by = |deps: Deps, sender: &str| -> Vec<Addr> {
+            let resp: RemovableByResp = from_binary(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::RemovableBy {
+                        sender: sender.to_owned(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            resp.admins
+        };
+
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                owner_must_be_admin: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            removable_by(deps.as_ref(), "owner"),
+            vec![Addr::unchecked("admin1")]
+        );
+
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                removal_threshold: Some(2),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(removable_by(deps.as_ref(), "owner"), Vec::<Addr>::new());
+        assert_eq!(removable_by(deps.as_ref(), "admin1"), Vec::<Addr>::new());
+    }
+    #[test] // this test checks an approval from an admin who left since no longer counts towards the threshold
+    fn removal_approvals_from_former_admins_dont_count() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec![
+                    "admin1".to_owned(),
+                    "admin2".to_owned(),
+                    "admin3".to_owned(),
+                    "admin4".to_owned(),
+                ],
+                removal_threshold: Some(2),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let approve = ExecuteMsg::ApproveRemove {
+            addr: "admin3".to_owned(),
+        };
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::ProposeRemove {
+                addr: "admin3".to_owned(),
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::Leave {},
+        )
+        .unwrap();
 
-//     match msg {
-//         Greet {} => to_binary(&query::greet()?),
-//     }
-// }
+        let resp = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin2", &[]),
+            approve.clone(),
+        )
+        .unwrap();
+        assert_eq!(resp.attributes, vec![Attribute::new("approvals", "1")]);
+        assert!(ADMINS.has(&deps.storage, &Addr::unchecked("admin3")));
 
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    use QueryMsg::*;
+        execute(deps.as_mut(), mock_env(), mock_info("admin4", &[]), approve).unwrap();
+        assert!(!ADMINS.has(&deps.storage, &Addr::unchecked("admin3")));
 
-    match msg {
-        Greet {} => to_binary(&query::greet()?),
-        AdminsList {} => to_binary(&query::admins_list(deps)?),
+        // the owner's approval counts even though the owner isn't an admin
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::ProposeRemove {
+                addr: "admin4".to_owned(),
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin2", &[]),
+            ExecuteMsg::ApproveRemove {
+                addr: "admin4".to_owned(),
+            },
+        )
+        .unwrap();
+        assert!(!ADMINS.has(&deps.storage, &Addr::unchecked("admin4")));
     }
-}
-
+    #[test] // this test checks CheckIntegrity flags an owner missing from the admins when owner_must_be_admin is set
+    fn check_integrity_owner_must_be_admin() {
+        let mut deps = mock_dependencies();
 
-pub fn execute(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    msg: ExecuteMsg,
-) -> Result<Response, ContractError> {
-    use ExecuteMsg::*;
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                owner_must_be_admin: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let check = |deps: Deps| -> IntegrityResp {
+            from_binary(&query(deps, mock_env(), QueryMsg::CheckIntegrity {}).unwrap()).unwrap()
+        };
+        assert!(check(deps.as_ref()).ok);
 
-    match msg {
-        AddMembers { admins } => exec::add_members(deps, info, admins),
-        Leave {} => exec::leave(deps, info).map_err(Into::into),
+        remove_admin_fully(&mut deps.storage, &deps.api, &Addr::unchecked("owner")).unwrap();
+        assert_eq!(
+            check(deps.as_ref()),
+            IntegrityResp {
+                ok: false,
+                problems: vec![
+                    "owner owner is not an admin although owner_must_be_admin is set".to_owned()
+                ],
+            }
+        );
     }
-}
+    #[test] // this test takes can_add away from supers and checks they can't invite or propose admins either
+    fn invites_and_proposals_need_can_add() {
+        let mut deps = mock_dependencies();
 
-mod exec {
-    use super::*;
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["super1".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SetRoleCapabilities {
+                role: Role::Super,
+                capabilities: Capabilities {
+                    can_add: false,
+                    can_remove: true,
+                    can_pause: false,
+                    can_config: false,
+                },
+            },
+        )
+        .unwrap();
 
-    pub fn add_members(
-        deps: DepsMut,
-        info: MessageInfo,
-        admins: Vec<String>,
-    ) -> Result<Response, ContractError> {
-        let mut curr_admins = ADMINS.load(deps.storage)?;
-        if !curr_admins.contains(&info.sender) {
-            return Err(ContractError::Unauthorized {
-                sender: info.sender,
-            });
-        }
+        let unauthorized = ContractError::Unauthorized {
+            sender: Addr::unchecked("super1"),
+        };
+        let invite = ExecuteMsg::CreateInvite {
+            code_hash: Binary::from(Sha256::digest(b"secret").to_vec()),
+            role: Role::Moderator,
+            weight: 1,
+            expires_at: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("super1", &[]),
+            invite.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, unauthorized);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("super1", &[]),
+            ExecuteMsg::ProposeAdmin {
+                addr: "newbie".to_owned(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, unauthorized);
 
-        let admins: StdResult<Vec<_>> = admins
-            .into_iter()
-            .map(|addr| deps.api.addr_validate(&addr))
-            .collect();
+        // the owner isn't bound by role capabilities
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), invite).unwrap();
+    }
+    #[test] // this test checks CanExecute reports messages the config switches off for everyone
+    fn can_execute_config_gates() {
+        let mut deps = mock_dependencies();
 
-        curr_admins.append(&mut admins?);
-        ADMINS.save(deps.storage, &curr_admins)?;
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                greeting_locked: true,
+                require_acceptance: true,
+                removal_threshold: Some(2),
+                ..Default::default()
+            },
+        )
+        .unwrap();
 
-        Ok(Response::new())
+        let gated = [
+            (
+                ExecuteMsg::SetGreeting {
+                    greeting: "gm".to_owned(),
+                },
+                ContractError::Frozen {},
+            ),
+            (
+                ExecuteMsg::AddMembers {
+                    admins: vec!["newbie".to_owned()],
+                },
+                ContractError::AcceptanceRequired {},
+            ),
+            (
+                ExecuteMsg::RemoveMembers {
+                    admins: vec!["admin2".to_owned()],
+                },
+                ContractError::ApprovalRequired {},
+            ),
+        ];
+        for (msg, err) in gated {
+            let resp: CanExecuteResp = from_binary(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::CanExecute {
+                        sender: "admin1".to_owned(),
+                        msg,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            assert_eq!(
+                resp,
+                CanExecuteResp {
+                    allowed: false,
+                    reason: Some(err.to_string()),
+                }
+            );
+        }
     }
-    pub fn leave(deps: DepsMut, info: MessageInfo) -> StdResult<Response> { // creating our function that allows admin to leave
-        ADMINS.update(deps.storage, move |admins| -> StdResult<_> {
-            let admins = admins
-                .into_iter()
-                .filter(|admin| *admin != info.sender)
-                .collect();
-            Ok(admins)
-        })?;
+    #[test] // this test checks a delegate approves and acts as the delegating admin, not as a second admin
+    fn delegate_acts_as_the_delegating_admin() {
+        let mut deps = mock_dependencies();
 
-        Ok(Response::new())
-    }
-}
-mod query {
-    use super::*;
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec![
+                    "admin1".to_owned(),
+                    "admin2".to_owned(),
+                    "admin3".to_owned(),
+                ],
+                removal_threshold: Some(2),
+                per_admin_cooldown: Some(60),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::Delegate {
+                to: "deputy".to_owned(),
+                until: mock_env().block.time.plus_seconds(3600),
+            },
+        )
+        .unwrap();
 
-    pub fn greet() -> StdResult<GreetResp> { // function for our greet query 
-        let resp = GreetResp {
-            message: "Hello World".to_owned(),
+        // the delegate shares admin1's cooldown
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(60);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::ProposeRemove {
+                addr: "admin3".to_owned(),
+            },
+        )
+        .unwrap();
+        let approve = ExecuteMsg::ApproveRemove {
+            addr: "admin3".to_owned(),
         };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("deputy", &[]),
+            approve.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Cooldown {
+                ready_at: env.block.time.plus_seconds(60)
+            }
+        );
 
-        Ok(resp)
-    }
-    pub fn admins_list(deps: Deps) -> StdResult<AdminsListResp> {
-        let admins = ADMINS.load(deps.storage)?;
-        let resp = AdminsListResp { admins };
-        Ok(resp)
-    }
-}
+        // and doesn't add a second approval on top of admin1's
+        env.block.time = env.block.time.plus_seconds(60);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("deputy", &[]),
+            approve,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::AlreadyApproved {
+                sender: Addr::unchecked("admin1")
+            }
+        );
+        assert!(ADMINS.has(&deps.storage, &Addr::unchecked("admin3")));
 
-#[cfg(test)]
-mod tests {
-    use cosmwasm_std::Addr;
-    use cw_multi_test::{App, ContractWrapper, Executor};
+        // what the delegate does counts as admin1's last action
+        env.block.time = env.block.time.plus_seconds(120);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("deputy", &[]),
+            ExecuteMsg::AddMembers {
+                admins: vec!["newbie".to_owned()],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            LAST_ACTION
+                .load(&deps.storage, &Addr::unchecked("admin1"))
+                .unwrap(),
+            env.block.time
+        );
+        assert!(!LAST_ACTION.has(&deps.storage, &Addr::unchecked("deputy")));
+    }
+    #[test] // this test checks PayoutPlan rejects the funds DonateTo would reject, including a repeated denom
+    fn payout_plan_validates_funds() {
+        let mut deps = mock_dependencies();
 
-    use super::*;
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                blocked_denoms: vec!["uscam".to_owned()],
+                min_donations: vec![("atom".to_owned(), Uint128::new(10))],
+                ..Default::default()
+            },
+        )
+        .unwrap();
 
-    #[test] // This test instantiates the contract with different admins to see if we get the same query 
-    fn instantiation() {
+        let cases = [
+            (vec![], ContractError::EmptyDonation {}),
+            (
+                coins(5, "x"),
+                ContractError::InvalidDenom {
+                    denom: "x".to_owned(),
+                },
+            ),
+            (
+                coins(5, "uscam"),
+                ContractError::DenomBlocked {
+                    denom: "uscam".to_owned(),
+                },
+            ),
+            (
+                coins(5, "atom"),
+                ContractError::DonationTooSmall {
+                    denom: "atom".to_owned(),
+                    min: Uint128::new(10),
+                },
+            ),
+            (
+                vec![coin(10, "atom"), coin(10, "atom")],
+                ContractError::DuplicateDenom {
+                    denom: "atom".to_owned(),
+                },
+            ),
+        ];
+        for (funds, err) in cases {
+            let query_err =
+                query(deps.as_ref(), mock_env(), QueryMsg::PayoutPlan { funds }).unwrap_err();
+            assert_eq!(
+                query_err,
+                StdError::generic_err(format!("admins::invalid_donation: {err}"))
+            );
+        }
+    }
+    #[test] // this test merges a roster longer than one AdminsList page and checks nobody is left behind
+    fn merge_from_pages_through_other() {
         let mut app = App::default();
 
         let code = ContractWrapper::new(execute, instantiate, query);
         let code_id = app.store_code(Box::new(code));
 
-        let addr = app
-            .instantiate_contract(
+        let mut instantiate_with = |admins: Vec<String>| {
+            app.instantiate_contract(
                 code_id,
                 Addr::unchecked("owner"),
-                &InstantiateMsg { admins: vec![] }, // initial message check
+                &InstantiateMsg {
+                    admins,
+                    ..Default::default()
+                },
                 &[],
                 "Contract",
                 None,
             )
-            .unwrap();
+            .unwrap()
+        };
+        let addr = instantiate_with(vec!["admin1".to_owned()]);
+        let other = instantiate_with((0..150).map(|i| format!("member{i}")).collect());
 
-        let resp: AdminsListResp = app
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::MergeFrom {
+                other: other.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: CountResp = app
             .wrap()
-            .query_wasm_smart(addr, &QueryMsg::AdminsList {})
+            .query_wasm_smart(addr, &QueryMsg::CountExact {})
             .unwrap();
+        assert_eq!(resp.count, 151);
+    }
+    #[test] // this test diffs against a roster longer than one AdminsList page
+    fn diff_from_pages_through_other() {
+        let mut app = App::default();
 
-        assert_eq!(resp, AdminsListResp { admins: vec![] });
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
 
-        let addr = app
-            .instantiate_contract(
+        let mut instantiate_with = |admins: Vec<String>| {
+            app.instantiate_contract(
                 code_id,
                 Addr::unchecked("owner"),
                 &InstantiateMsg {
-                    admins: vec!["admin1".to_owned(), "admin2".to_owned()], // second message check
+                    admins,
+                    ..Default::default()
                 },
                 &[],
-                "Contract 2",
+                "Contract",
                 None,
             )
-            .unwrap();
+            .unwrap()
+        };
+        let members: Vec<_> = (0..150).map(|i| format!("member{i}")).collect();
+        // admins joined in the same block are ordered by canonical address, take the one that comes last on `other`
+        let last = members
+            .iter()
+            .max_by_key(|member| MockApi::default().addr_canonicalize(member).unwrap().0)
+            .unwrap()
+            .clone();
+        let addr = instantiate_with(vec!["admin1".to_owned(), last]);
+        let other = instantiate_with(members);
 
-        let resp: AdminsListResp = app
+        let resp: DiffFromResp = app
             .wrap()
-            .query_wasm_smart(addr, &QueryMsg::AdminsList {})
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::DiffFrom {
+                    other: other.to_string(),
+                },
+            )
             .unwrap();
+        assert_eq!(resp.admins, vec![Addr::unchecked("admin1")]);
+    }
+    #[test] // this test checks CanJoin reports require_acceptance and a full weight cap
+    fn can_join_acceptance_and_weight_cap() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                require_acceptance: true,
+                max_total_weight: Some(2),
+                ..Default::default()
+            },
+        )
+        .unwrap();
 
+        let resp: CanJoinResp = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::CanJoin {
+                    addr: "newbie".to_owned(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
         assert_eq!(
             resp,
-            AdminsListResp {
-                admins: vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")],
+            CanJoinResp {
+                can: false,
+                reasons: vec![
+                    ContractError::AcceptanceRequired {}.to_string(),
+                    ContractError::WeightCapExceeded { max: 2 }.to_string(),
+                ],
             }
         );
     }
+    #[test] // this test checks paging after the cursor admin left fails with a prefixed error
+    fn admins_list_stale_cursor() {
+        let mut deps = mock_dependencies();
 
-    #[test] // this test wraps our query and verifys that the greet respone matches 
-    fn greet_query() {
-        let mut app = App::default();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec![
+                    "admin1".to_owned(),
+                    "admin2".to_owned(),
+                    "admin3".to_owned(),
+                ],
+                ..Default::default()
+            },
+        )
+        .unwrap();
 
-        let code = ContractWrapper::new(execute, instantiate, query);
-        let code_id = app.store_code(Box::new(code));
+        let page = |deps: Deps, start_after: Option<String>| {
+            let msg = QueryMsg::AdminsList {
+                start_after,
+                limit: Some(2),
+            };
+            query(deps, mock_env(), msg)
+        };
+        let first: AdminsListResp = from_binary(&page(deps.as_ref(), None).unwrap()).unwrap();
+        let cursor = first.admins.last().unwrap().clone();
 
-        let addr = app
-            .instantiate_contract(
-                code_id,
-                Addr::unchecked("owner"),
-                &InstantiateMsg { admins: vec![] },
-                &[],
-                "Contract",
-                None,
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(cursor.as_str(), &[]),
+            ExecuteMsg::Leave {},
+        )
+        .unwrap();
+
+        let err = page(deps.as_ref(), Some(cursor.to_string())).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(format!(
+                "admins::stale_cursor: {cursor} is no longer an admin, page again from the start"
+            ))
+        );
+    }
+    #[test] // this test checks Availability blocks only the flags whose message a config setting switches off
+    fn availability_config_gates() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                require_acceptance: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let availability = |deps: Deps| -> AvailabilityResp {
+            from_binary(&query(deps, mock_env(), QueryMsg::Availability {}).unwrap()).unwrap()
+        };
+        assert_eq!(
+            availability(deps.as_ref()),
+            AvailabilityResp {
+                can_add: false,
+                can_remove: true,
+                can_leave: true,
+                reasons: vec![format!("can_add: {}", ContractError::AcceptanceRequired {})],
+            }
+        );
+
+        let mut config = CONFIG.load(&deps.storage).unwrap();
+        config.require_acceptance = false;
+        config.removal_threshold = Some(2);
+        CONFIG.save(&mut deps.storage, &config).unwrap();
+        assert_eq!(
+            availability(deps.as_ref()),
+            AvailabilityResp {
+                can_add: true,
+                can_remove: false,
+                can_leave: true,
+                reasons: vec![format!(
+                    "can_remove: {}",
+                    ContractError::ApprovalRequired {}
+                )],
+            }
+        );
+    }
+    #[test] // this test corrupts the join-time index and the per-admin maps and checks CheckIntegrity flags each entry
+    fn check_integrity_index_and_orphans() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let check = |deps: Deps| -> IntegrityResp {
+            from_binary(&query(deps, mock_env(), QueryMsg::CheckIntegrity {}).unwrap()).unwrap()
+        };
+        assert!(check(deps.as_ref()).ok);
+
+        // admin1's entry moves to the wrong join time, admin2's is dropped and a stranger gets one
+        let joined = ADMINS
+            .load(&deps.storage, &Addr::unchecked("admin1"))
+            .unwrap()
+            .joined;
+        let canonical = |addr: &str| deps.api.addr_canonicalize(addr).unwrap();
+        let (admin1, admin2, stranger) = (
+            canonical("admin1"),
+            canonical("admin2"),
+            canonical("stranger"),
+        );
+        ADMINS_BY_JOINED.remove(&mut deps.storage, (joined.nanos(), admin1.as_slice()));
+        ADMINS_BY_JOINED
+            .save(
+                &mut deps.storage,
+                (joined.nanos() + 1, admin1.as_slice()),
+                &Addr::unchecked("admin1"),
+            )
+            .unwrap();
+        ADMINS_BY_JOINED.remove(&mut deps.storage, (joined.nanos(), admin2.as_slice()));
+        ADMINS_BY_JOINED
+            .save(
+                &mut deps.storage,
+                (joined.nanos(), stranger.as_slice()),
+                &Addr::unchecked("stranger"),
             )
             .unwrap();
 
-        let resp: GreetResp = app
-            .wrap()
-            .query_wasm_smart(addr, &QueryMsg::Greet {})
+        let stranger = Addr::unchecked("stranger");
+        LABELS
+            .save(&mut deps.storage, &stranger, &"ghost".to_owned())
+            .unwrap();
+        DELEGATIONS
+            .save(
+                &mut deps.storage,
+                &stranger,
+                &(Addr::unchecked("admin1"), mock_env().block.time),
+            )
+            .unwrap();
+        PENDING_REMOVALS
+            .save(
+                &mut deps.storage,
+                &stranger,
+                &vec![Addr::unchecked("admin1")],
+            )
             .unwrap();
 
+        let resp = check(deps.as_ref());
+        assert!(!resp.ok);
         assert_eq!(
-            resp,
-            GreetResp {
-                message: "Hello World".to_owned() // message to be matched 
+            resp.problems,
+            vec![
+                "join-time index entry stored for non-admin stranger".to_owned(),
+                "join-time index entry for admin1 doesn't match its join time".to_owned(),
+                "admin admin1 is missing from the join-time index".to_owned(),
+                "admin admin2 is missing from the join-time index".to_owned(),
+                "label stored for non-admin stranger".to_owned(),
+                "delegation stored for non-admin stranger".to_owned(),
+                "pending removal stored for non-admin stranger".to_owned(),
+            ]
+        );
+    }
+    #[test] // this test checks a proposal lapses once the admin who made it has left
+    fn accept_admin_after_proposer_left() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::ProposeAdmin {
+                addr: "newbie".to_owned(),
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::Leave {},
+        )
+        .unwrap();
+
+        let resp: PendingSelfAcceptResp =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::PendingSelfAccept {}).unwrap())
+                .unwrap();
+        assert!(resp.pending.is_empty());
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("newbie", &[]),
+            ExecuteMsg::AcceptAdmin {},
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ProposerInactive {
+                addr: Addr::unchecked("newbie"),
+                proposer: Addr::unchecked("admin1"),
             }
         );
+        assert!(!ADMINS.has(&deps.storage, &Addr::unchecked("newbie")));
     }
-    #[test] // this test tells us wether our not the address is authorized to execute a message, add or remove addresses and leave as admin
-    fn unauthorized() { 
+    #[test] // this test checks CanExecute applies RemoveMembers' rules on who can remove the owner
+    fn can_execute_remove_owner() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["owner".to_owned(), "admin1".to_owned()],
+                owner_must_be_admin: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let can_execute = |deps: Deps, sender: &str| -> CanExecuteResp {
+            let msg = QueryMsg::CanExecute {
+                sender: sender.to_owned(),
+                msg: ExecuteMsg::RemoveMembers {
+                    admins: vec!["owner".to_owned()],
+                },
+            };
+            from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+        };
+        assert_eq!(
+            can_execute(deps.as_ref(), "admin1").reason,
+            Some(
+                ContractError::Unauthorized {
+                    sender: Addr::unchecked("admin1")
+                }
+                .to_string()
+            )
+        );
+        assert_eq!(
+            can_execute(deps.as_ref(), "owner").reason,
+            Some(ContractError::OwnerMustBeAdmin {}.to_string())
+        );
+    }
+    #[test] // this test checks the fallback admin brought in when the last admin leaves gets a welcome message
+    fn welcome_fallback_on_leave() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                fallback_admin: Some("fallback".to_owned()),
+                notify_on_join: Some("notifier".to_owned()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let resp = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::Leave {},
+        )
+        .unwrap();
+        let welcome: CosmosMsg = WasmMsg::Execute {
+            contract_addr: "notifier".to_owned(),
+            msg: to_binary(&NotifierExecuteMsg::Welcome {
+                admin: Addr::unchecked("fallback"),
+            })
+            .unwrap(),
+            funds: vec![],
+        }
+        .into();
+        assert!(resp.messages.iter().any(|sub| sub.msg == welcome));
+    }
+    #[test] // this test checks CanExecute reports the tenure, owner, transfer delay and proposal expiry checks
+    fn can_execute_sender_checks() {
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec!["admin1".to_owned()],
+                owner_must_be_admin: true,
+                min_tenure: Some(100),
+                transfer_delay: 60,
+                proposal_ttl: Some(10),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::TransferOwnership {
+                new_owner: "owner2".to_owned(),
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::ProposeAdmin {
+                addr: "newbie".to_owned(),
+            },
+        )
+        .unwrap();
+
+        let reason = |deps: Deps, env: Env, sender: &str, msg: ExecuteMsg| -> Option<String> {
+            let msg = QueryMsg::CanExecute {
+                sender: sender.to_owned(),
+                msg,
+            };
+            let resp: CanExecuteResp = from_binary(&query(deps, env, msg).unwrap()).unwrap();
+            resp.reason
+        };
+        let now = mock_env().block.time;
+        assert_eq!(
+            reason(deps.as_ref(), mock_env(), "admin1", ExecuteMsg::Leave {}),
+            Some(
+                ContractError::TenureNotMet {
+                    ready_at: now.plus_seconds(100)
+                }
+                .to_string()
+            )
+        );
+        assert_eq!(
+            reason(deps.as_ref(), mock_env(), "owner", ExecuteMsg::Leave {}),
+            Some(ContractError::OwnerMustBeAdmin {}.to_string())
+        );
+        assert_eq!(
+            reason(
+                deps.as_ref(),
+                mock_env(),
+                "owner2",
+                ExecuteMsg::AcceptOwnership {}
+            ),
+            Some(
+                ContractError::TransferNotReady {
+                    ready_at: now.plus_seconds(60)
+                }
+                .to_string()
+            )
+        );
+
+        let mut later = mock_env();
+        later.block.time = now.plus_seconds(10);
+        assert_eq!(
+            reason(
+                deps.as_ref(),
+                mock_env(),
+                "newbie",
+                ExecuteMsg::AcceptAdmin {}
+            ),
+            None
+        );
+        assert_eq!(
+            reason(deps.as_ref(), later, "newbie", ExecuteMsg::AcceptAdmin {}),
+            Some(
+                ContractError::ProposalExpired {
+                    addr: Addr::unchecked("newbie")
+                }
+                .to_string()
+            )
+        );
+    }
+    #[test] // this test instantiates with blocked and malformed denoms in the funds and checks both are rejected
+    fn instantiate_donation_validated() {
+        let msg = InstantiateMsg {
+            admins: vec!["admin1".to_owned()],
+            blocked_denoms: vec!["scam".to_owned()],
+            ..Default::default()
+        };
+
+        let mut deps = mock_dependencies();
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &coins(10, "scam")),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DenomBlocked {
+                denom: "scam".to_owned()
+            }
+        );
+
+        let mut deps = mock_dependencies();
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &coins(10, "1nvalid")),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidDenom {
+                denom: "1nvalid".to_owned()
+            }
+        );
+
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &coins(10, "atom")),
+            msg,
+        )
+        .unwrap();
+        assert_eq!(
+            TOTAL_DONATED.load(&deps.storage, "atom").unwrap(),
+            Uint128::new(10)
+        );
+    }
+    #[test] // this test recovers more admins than max_admins allows and checks the roster stays empty
+    fn recover_respects_max_admins() {
         let mut app = App::default();
 
-        let code = ContractWrapper::new(execute, instantiate, query);
+        let code = ContractWrapper::new(execute, instantiate, query).with_sudo(sudo);
         let code_id = app.store_code(Box::new(code));
 
         let addr = app
             .instantiate_contract(
                 code_id,
                 Addr::unchecked("owner"),
-                &InstantiateMsg { admins: vec![] },
+                &InstantiateMsg {
+                    admins: vec![],
+                    max_admins: Some(1),
+                    ..Default::default()
+                },
                 &[],
                 "Contract",
                 None,
             )
             .unwrap();
 
-        let err = app
-            .execute_contract(
-                Addr::unchecked("user"),
-                addr,
-                &ExecuteMsg::AddMembers {
-                    admins: vec!["user".to_owned()],
-                },
-                &[],
-            )
-            .unwrap_err();
-
+        let recover = SudoMsg::Recover {
+            admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+        };
+        let err = app.wasm_sudo(addr.clone(), &recover).unwrap_err();
         assert_eq!(
-            ContractError::Unauthorized {
-                sender: Addr::unchecked("user")
-            },
+            ContractError::TooManyAdmins { max: 1 },
             err.downcast().unwrap()
         );
+
+        let resp: IsBrickedResp = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &QueryMsg::IsBricked {})
+            .unwrap();
+        assert!(resp.bricked);
+
+        // a roster within the cap is recovered as usual
+        let recover = SudoMsg::Recover {
+            admins: vec!["admin1".to_owned()],
+        };
+        app.wasm_sudo(addr, &recover).unwrap();
     }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, StdError};
+use cosmwasm_std::{Addr, StdError, Timestamp, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -7,4 +7,104 @@ pub enum ContractError {
     StdError(#[from] StdError),
     #[error("{sender} is not contract admin")]
     Unauthorized { sender: Addr },
+    #[error("{sender} is not contract owner")]
+    NotOwner { sender: Addr },
+    #[error("action is frozen")]
+    Frozen {},
+    #[error("nothing to sweep")]
+    NothingToSweep {},
+    #[error("invite already exists")]
+    InviteExists {},
+    #[error("invite not found")]
+    InviteNotFound {},
+    #[error("invite expired")]
+    InviteExpired {},
+    #[error("{addr} is already an admin")]
+    AlreadyAdmin { addr: Addr },
+    #[error("{addr} is not an admin")]
+    NotAdmin { addr: Addr },
+    #[error("insufficient stake delegated")]
+    InsufficientStake {},
+    #[error("invalid address: {addr:?}")]
+    InvalidAddress { addr: String },
+    #[error("no ownership transfer pending")]
+    NoPendingOwner {},
+    #[error("{sender} is not the pending owner")]
+    NotPendingOwner { sender: Addr },
+    #[error("ownership transfer can't be accepted before {ready_at}")]
+    TransferNotReady { ready_at: Timestamp },
+    #[error("minimum tenure not met, can leave from {ready_at}")]
+    TenureNotMet { ready_at: Timestamp },
+    #[error("new expiry is earlier than the current one")]
+    InvalidExpiry {},
+    #[error("contract is under maintenance until {until}")]
+    Maintenance { until: Timestamp },
+    #[error("an owner is required to migrate a contract without one")]
+    OwnerRequired {},
+    #[error("roster can only be imported or recovered into a contract without admins")]
+    AlreadyPopulated {},
+    #[error("admin is rate limited until {ready_at}")]
+    Cooldown { ready_at: Timestamp },
+    #[error("{addr} is not on the allowlist")]
+    NotAllowlisted { addr: Addr },
+    #[error("no donation recipients given")]
+    NoRecipients {},
+    #[error("{sender} has not been proposed as admin")]
+    NotProposed { sender: Addr },
+    #[error("new admins have to be proposed and accept themselves")]
+    AcceptanceRequired {},
+    #[error("label is longer than {max} characters")]
+    LabelTooLong { max: u32 },
+    #[error("the owner can't remove themself")]
+    CannotRemoveSelf {},
+    #[error("the last admin can't be removed")]
+    LastAdmin {},
+    #[error("invalid denom {denom}")]
+    InvalidDenom { denom: String },
+    #[error("admins have to be removed through ProposeRemove/ApproveRemove")]
+    ApprovalRequired {},
+    #[error("removal of {addr} is already pending")]
+    RemovalPending { addr: Addr },
+    #[error("no pending removal of {addr}")]
+    NoPendingRemoval { addr: Addr },
+    #[error("{sender} already approved this removal")]
+    AlreadyApproved { sender: Addr },
+    #[error("the owner has to stay an admin")]
+    OwnerMustBeAdmin {},
+    #[error("admin count is out of sync with the stored admins")]
+    CountUnderflow {},
+    #[error("donations in {denom} are not accepted")]
+    DenomBlocked { denom: String },
+    #[error("expected instantiation by {expected}, got {sender}")]
+    UnexpectedSender { expected: Addr, sender: Addr },
+    #[error("contract is read-only")]
+    ReadOnly {},
+    #[error("admin was updated in between, expected version {expected} but it is {actual}")]
+    StaleUpdate { expected: u64, actual: u64 },
+    #[error("total admin weight would exceed {max}")]
+    WeightCapExceeded { max: u64 },
+    #[error("donation is empty")]
+    EmptyDonation {},
+    #[error("no freeze proposed")]
+    NoPendingFreeze {},
+    #[error("an admin can't delegate to themself")]
+    CannotDelegateToSelf {},
+    #[error("an active delegation exists, revoke it first")]
+    DelegationExists {},
+    #[error("no delegation to revoke")]
+    NoDelegation {},
+    #[error("donations in {denom} have to be at least {min}")]
+    DonationTooSmall { denom: String, min: Uint128 },
+    #[error("the proposal for {addr} has expired")]
+    ProposalExpired { addr: Addr },
+    #[error("{proposer} proposed {addr} but can no longer add admins")]
+    ProposerInactive { addr: Addr, proposer: Addr },
+    #[error("no expired admins to process")]
+    NothingExpired {},
+    #[error("at most {max} admins are allowed")]
+    TooManyAdmins { max: u32 },
+    #[error("{denom} is listed more than once")]
+    DuplicateDenom { denom: String },
+    #[error("{addr} is listed more than once")]
+    DuplicateAddress { addr: Addr },
 }
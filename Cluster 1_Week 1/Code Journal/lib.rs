@@ -1,10 +1,11 @@
 use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
-use msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use error::ContractError;
+use msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
 
 mod contract;
 mod error;
-mod msg;
-mod state;
+pub mod msg;
+pub mod state;
 
 #[entry_point]
 pub fn instantiate( //the entry point of our contract that contains dependencies, state and message info. Only called once to deploy the contract
@@ -12,12 +13,17 @@ pub fn instantiate( //the entry point of our contract that contains dependencies
     env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     contract::instantiate(deps, env, info, msg)
 }
 
 #[entry_point] //defining our execute function that returns message response or error message based on the values in the arguments. Call be called multiple times
-pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
     contract::execute(deps, env, info, msg)
 }
 
@@ -26,5 +32,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     contract::query(deps, env, msg)
 }
 
+#[entry_point] // called when the contract code is upgraded, backfills state older versions didn't store
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    contract::migrate(deps, env, msg)
+}
 
-
+#[entry_point] // called by governance only, toggles the read-only mode
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    contract::sudo(deps, env, msg)
+}
@@ -1,26 +1,567 @@
-use cosmwasm_std::Addr;
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Binary, Coin, Timestamp, Uint128};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
-pub struct InstantiateMsg { // only the admin is able to instantiate the contract
+use crate::state::{Capabilities, Config, RateGuard, Role};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default, JsonSchema)]
+pub struct InstantiateMsg { // only the admin is able to instantiate the contract, funds sent along are split between the initial admins
     pub admins: Vec<String>,
+    pub log_contract: Option<String>, // optional contract that receives a log message on every mutation
+    #[serde(default)]
+    pub greeting_locked: bool, // when true the greeting can never be changed
+    #[serde(default)]
+    pub strict_limits: bool, // when true queries reject limits above the maximum instead of clamping
+    pub cw4_group: Option<String>, // optional cw4-group contract mirroring the admin set
+    pub min_delegation: Option<Uint128>, // minimum bonded stake an admin added by an exec must have delegated
+    pub default_role: Option<Role>, // role given to admins added through AddMembers, defaults to Super
+    #[serde(default)]
+    pub transfer_delay: u64, // seconds a proposed owner has to wait before accepting ownership
+    pub min_tenure: Option<u64>, // seconds an admin has to stay before they are allowed to leave
+    pub migration_source: Option<String>, // old admin contract allowed to import its roster here
+    pub per_admin_cooldown: Option<u64>, // seconds an admin has to wait between two execs
+    pub allowlist_contract: Option<String>, // compliance contract answering AllowlistQueryMsg::IsAllowed
+    #[serde(default)]
+    pub require_acceptance: bool, // when true AddMembers and MergeFrom are disabled and new admins have to self-accept, see Config
+    pub fallback_admin: Option<String>, // becomes the sole admin if the last admin is removed, or right away without initial admins
+    pub max_label_len: Option<u32>, // defaults to 64 characters
+    #[serde(default)]
+    pub labels: Vec<(String, String)>, // (admin, label) for admins in `admins`
+    pub notify_on_join: Option<String>, // notifier contract receiving NotifierExecuteMsg::Welcome per new admin
+    pub removal_threshold: Option<u32>, // when set admins are removed through ProposeRemove/ApproveRemove only
+    #[serde(default)]
+    pub owner_must_be_admin: bool, // when true the owner is added as admin and can never be removed
+    #[serde(default)]
+    pub role_capabilities: Vec<(Role, Capabilities)>, // overrides Role::default_capabilities
+    #[serde(default)]
+    pub blocked_denoms: Vec<String>, // donations in any of these denoms are rejected
+    pub expected_sender: Option<String>, // instantiation fails unless sent by this address, guards against front-running
+    pub max_total_weight: Option<u64>, // no roster change may push the summed admin weight above this
+    #[serde(default)]
+    pub freeze_delay: u64, // seconds a proposed freeze waits before taking effect
+    #[serde(default)]
+    pub min_donations: Vec<(String, Uint128)>, // (denom, minimum amount) DonateTo and the funds sent to instantiate are held to
+    pub proposal_ttl: Option<u64>, // seconds before an unfinished ProposeAdmin or ProposeRemove expires, never when unset
+    pub max_admins: Option<u32>, // no roster change may push the number of admins above this, `admins` included
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default, JsonSchema)]
+pub struct MigrateMsg {
+    pub owner: Option<String>, // required when the contract has no owner stored yet, ignored otherwise
+    #[serde(default)]
+    pub allow_non_admin_owner: bool, // by default the backfilled owner has to be an existing admin
+    #[serde(default)]
+    pub prune_expired: bool, // remove every admin that is expired at the migration block
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub enum SudoMsg { // sent by the chain's governance, never by an account
+    SetReadOnly { read_only: bool }, // blocks every exec, the owner's included, until lifted again
+    Recover { admins: Vec<String> }, // repopulates an empty roster, see IsBricked
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 pub enum ExecuteMsg { // The admin can also add additional admins or remove themself as admin
     AddMembers { admins: Vec<String> },
+    RemoveMembers { admins: Vec<String> }, // owner removes anyone, supers anyone but the owner
     Leave {},
+    SetGreeting { greeting: String },
+    Sweep { denom: String, to: String }, // owner sends the whole contract balance of `denom` to `to`
+    CreateInvite {
+        code_hash: Binary, // sha256 of the secret invite code, so the code itself never hits the chain before use
+        role: Role,
+        weight: u64,
+        expires_at: Option<u64>,
+    }, // owner or super admins with can_add
+    RedeemInvite { code: String },
+    SetAnnotation {
+        addr: String,
+        key: String,
+        value: String,
+        expected_version: u64, // the admin's version as last read, see StaleUpdate
+    }, // owner or super admins
+    ClearAnnotations {
+        addr: String,
+        expected_version: u64, // the admin's version as last read, see StaleUpdate
+    }, // owner or the admin themselves
+    UpdateConfig { default_role: Option<Role> }, // owner only, fields left as None are unchanged
+    TransferOwnership { new_owner: String },      // owner proposes a new owner
+    AcceptOwnership {},                           // proposed owner accepts once the transfer delay passed
+    ExtendExpiry {
+        addr: String,
+        new_expires_at: u64, // 0 removes the expiry
+    },
+    SetMaintenance { until: Option<Timestamp> }, // owner only, None ends the window
+    ExportRoster {
+        to: String,
+        #[serde(default)]
+        freeze: bool, // freeze this contract once the roster is sent
+    },
+    ImportRoster { admins: Vec<AdminRecord> }, // only accepted from the configured migration source, into an empty roster
+    DonateTo { recipients: Vec<String> }, // splits the sent funds equally, the remainder stays in the contract
+    ProposeAdmin { addr: String }, // needs can_add like AddMembers, the proposed address has to accept itself
+    AcceptAdmin {},
+    SetLabel {
+        addr: String,
+        label: String,
+        expected_version: u64,
+    }, // owner or super admins
+    SetMyLabel { label: String, expected_version: u64 },
+    OwnerRemove { addr: String }, // owner only, bypasses roles but never removes the owner or the last admin
+    SwapRoles { a: String, b: String }, // owner or super admins
+    PruneInactive { duration: u64 }, // owner only, removes the admins InactiveSince lists except the owner and the last admin
+    ProposeRemove { addr: String }, // owner or super admins, counts as the first approval
+    ApproveRemove { addr: String }, // owner or super admins, removes `addr` once removal_threshold approvals are in
+    SetRank {
+        addr: String,
+        rank: u32,
+        expected_version: u64,
+    }, // owner or super admins, ranks may tie
+    SetRoleCapabilities {
+        role: Role,
+        capabilities: Capabilities,
+    }, // owner only
+    SetRateGuard { max_changes: u64, window: u64 }, // owner only, a window of 0 turns the guard off
+    Delegate { to: String, until: Timestamp }, // admin only, `to` gets the sender's add/remove rights until `until`
+    RevokeDelegation {}, // ends the sender's delegation right away
+    SetLabels { entries: Vec<(String, String)> }, // owner only, (admin, label) pairs, addresses that aren't admins are skipped
+    ProposeFreeze {}, // owner only, freezes the contract once freeze_delay has passed
+    CancelFreeze {},  // owner only, drops a proposed freeze that hasn't taken effect yet
+    MergeFrom { other: String }, // owner only, adds every admin AdminsList returns on `other`, page by page
+    Reorder { order: Vec<String> }, // owner only, ranks the listed admins 0, 1, 2... in the given order
+    PruneProposals {}, // owner or super admins, drops every proposal past proposal_ttl
+    PokeExpiry {}, // anyone, for keepers: removes every expired admin, fails if there is none
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct InstantiateResult { // set as the instantiate response data
+    pub owner: Addr,
+    pub admin_count: u32,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct AdminRecord { // an admin as carried between contracts by ExportRoster/ImportRoster
+    pub addr: String,
+    pub joined: Timestamp,
+    pub role: Role,
+    pub weight: u64,
+    pub expires_at: u64,
+    pub added_by: String,
+    pub label: Option<String>,
+    #[serde(default)]
+    pub annotations: Vec<(String, String)>, // the admin's annotations as (key, value) pairs
+    #[serde(default)]
+    pub rank: u32,
+    #[serde(default)]
+    pub version: u64,
+    #[serde(default)]
+    pub joined_height: u64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct RemoveResult { // set as the RemoveMembers response data
+    pub removed: Vec<Addr>,
+    pub skipped: Vec<Addr>, // targets that weren't admins
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 pub struct GreetResp {
    pub message: String,
 }
 
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct AdminsListResp {
+    pub admins: Vec<Addr>,
+}
+
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema, QueryResponses)]
 pub enum QueryMsg {
+    #[returns(GreetResp)]
     Greet {}, // Without "{}" the JSON would serialize to just a string type. It is a good habit to always add the {} to serde serializable empty enum variants - for better JSON representation. 
-    AdminsList {},
+    #[returns(AdminsListResp)]
+    AdminsList {
+        start_after: Option<String>, // last admin of the previous page, fails with admins::stale_cursor once they left
+        limit: Option<u32>,          // defaults to and is capped at 100
+    },
+    #[returns(ReconcileResp)]
+    Reconcile { expected: Vec<String> }, // compares the admin set against an off-chain roster
+    #[returns(AdminsPreviewResp)]
+    AdminsPreview { limit: u32 },        // first `limit` admins plus the total count
+    #[returns(InvitesResp)]
+    Invites {
+        start_after: Option<Binary>, // code hash to continue after
+        limit: Option<u32>,
+    },
+    #[returns(AnnotationsResp)]
+    Annotations { addr: String },
+    #[returns(AdminsByRoleResp)]
+    AdminsByRole {},
+    #[returns(IndexOfResp)]
+    IndexOf { addr: String }, // zero-based position in the canonical admin ordering
+    #[returns(StateFingerprintResp)]
+    StateFingerprint {},      // cheap change detection over the roster and config
+    #[returns(PendingOwnerResp)]
+    PendingOwnerReadyAt {},
+    #[returns(ExpiringSoonResp)]
+    ExpiringSoon { within_blocks: u64 }, // active admins expiring within the next `within_blocks` blocks
+    #[returns(NextExpiryResp)]
+    NextExpiry {}, // the active admin expiring soonest, for scheduling PokeExpiry
+    #[returns(BothAdminsResp)]
+    BothAdmins { a: String, b: String },
+    #[returns(CreatedAtResp)]
+    CreatedAt {},
+    #[returns(AvailabilityResp)]
+    Availability {}, // the gates and config switches that hold for every sender, per-admin ones like the cooldown aren't covered
+    #[returns(IntegrityResp)]
+    CheckIntegrity {},
+    #[returns(PendingSelfAcceptResp)]
+    PendingSelfAccept {}, // proposals AcceptAdmin would still take, leaving out expired ones and those whose proposer can no longer add admins
+    #[returns(AddedByResp)]
+    AddedBy { addr: String },
+    #[returns(RemovableByResp)]
+    RemovableBy { sender: String },
+    #[returns(CanExecuteResp)]
+    CanExecute { sender: String, msg: ExecuteMsg }, // runs the exec's authorization checks without executing it
+    #[returns(CanJoinResp)]
+    CanJoin { addr: String }, // every check AddMembers would run on `addr`, for client preflight
+    #[returns(BlockInfoResp)]
+    BlockInfo {}, // echoes env.block, for checking time-gated behaviour
+    #[returns(SelfAddressResp)]
+    SelfAddress {}, // env.contract.address, for clients composing this contract
+    #[returns(ConfigMatchesResp)]
+    ConfigMatches { expected: Box<Config> },
+    #[returns(DelegationsResp)]
+    Delegations {},
+    #[returns(DiffFromResp)]
+    DiffFrom { other: String }, // admins here that aren't among the admins AdminsList returns on `other`, read page by page
+    #[returns(IntersectionResp)]
+    Intersection { addrs: Vec<String> }, // at most 100 addresses
+    #[returns(StateExport)]
+    Export {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(EffectiveAuthorityResp)]
+    EffectiveAuthority { sender: String },
+    #[returns(PayoutPlanResp)]
+    PayoutPlan { funds: Vec<Coin> }, // how DonateTo to every active admin would split `funds`, errors where DonateTo would reject them
+    #[returns(FreezeStatusResp)]
+    FreezeStatus {},
+    #[returns(StatusCountsResp)]
+    StatusCounts {},
+    #[returns(IsBrickedResp)]
+    IsBricked {},
+    #[returns(AdminsAddedBetweenResp)]
+    AdminsAddedBetween { from_height: u64, to_height: u64 }, // both ends inclusive
+    #[returns(PolicyResp)]
+    Policy {}, // every authorization-relevant setting at once, for audits
+    #[returns(TotalDonatedResp)]
+    TotalDonated {},
+    #[returns(CountResp)]
+    Count {}, // the cached ADMIN_COUNT
+    #[returns(CountResp)]
+    CountExact {}, // counts the ADMINS keys one by one, slow but authoritative, for reconciling Count
+    #[returns(AdminsByRankResp)]
+    AdminsByRank {},
+    #[returns(PendingRemovalsResp)]
+    PendingRemovals {},
+    #[returns(PrunableProposalsResp)]
+    PrunableProposals {}, // proposals past proposal_ttl that PruneProposals would drop
+    #[returns(InactiveSinceResp)]
+    InactiveSince { duration: u64 }, // admins without an exec, or since joining, in the last `duration` seconds
+    #[returns(AdminsByMinWeightResp)]
+    AdminsByMinWeight {
+        min: u64,
+        start_after: Option<String>, // address to continue after, pages go in address order
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct AdminsPreviewResp {
+    pub sample: Vec<Addr>,
+    pub total: u32,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct ReconcileResp {
+    pub missing: Vec<Addr>, // expected but not admins
+    pub extra: Vec<Addr>,   // admins but not expected
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct InviteEntry { // invite metadata, the secret code is never stored
+    pub code_hash: Binary,
+    pub role: Role,
+    pub weight: u64,
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct InvitesResp {
+    pub invites: Vec<InviteEntry>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct AnnotationsResp {
+    pub annotations: Vec<(String, String)>, // (key, value) ordered by key
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct AdminsByRoleResp {
+    pub roles: Vec<(Role, Vec<Addr>)>, // roles in declaration order, admins in the canonical order
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct IndexOfResp {
+    pub index: Option<u32>, // None if the address is not an admin
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct StateFingerprintResp {
+    pub fingerprint: Binary, // sha256, changes whenever an admin or the config changes
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct PendingOwnerResp {
+    pub pending_owner: Option<Addr>,
+    pub ready_at: Option<Timestamp>, // earliest block time AcceptOwnership succeeds
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct ExpiringSoonResp {
+    pub admins: Vec<(Addr, u64)>, // (admin, expiry height), soonest first
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct NextExpiryResp {
+    pub next: Option<(Addr, u64)>, // (admin, expiry height), None when no active admin expires
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct BothAdminsResp {
+    pub a_is_admin: bool,
+    pub b_is_admin: bool,
+    pub both: bool,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct CreatedAtResp {
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct AvailabilityResp {
+    pub can_add: bool,
+    pub can_remove: bool,
+    pub can_leave: bool,
+    pub reasons: Vec<String>, // "<flag>: <error>" for each blocked flag, the error its message would currently fail with
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct CanJoinResp {
+    pub can: bool,
+    pub reasons: Vec<String>, // the error each failed check would currently fail with
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct IntegrityResp {
+    pub ok: bool,
+    pub problems: Vec<String>, // one human readable line per broken invariant
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct PendingSelfAcceptResp {
+    pub pending: Vec<(Addr, Addr)>, // (proposed address, admin who proposed it)
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct AddedByResp {
+    pub added_by: Option<Addr>, // None if the address is not an admin
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct RemovableByResp {
+    pub admins: Vec<Addr>, // in the canonical admin ordering
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct CanExecuteResp {
+    pub allowed: bool,
+    pub reason: Option<String>, // the error the exec would fail with, None when allowed
 }
 
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct BlockInfoResp {
+    pub height: u64,
+    pub time: Timestamp,
+    pub chain_id: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct SelfAddressResp {
+    pub address: Addr,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct ConfigMatchesResp {
+    pub differing: Vec<String>, // names of the config fields that don't match, empty if none
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct StateExport { // snapshot for backups, page through `admins` with start_after
+    pub config: Config,
+    pub owner: Addr,
+    pub greeting: String,
+    pub admin_count: u32,
+    pub roster_changes: u64,
+    pub admins: Vec<AdminRecord>, // one page, ordered by address
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct DiffFromResp {
+    pub admins: Vec<Addr>, // in the canonical admin ordering
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct IntersectionResp {
+    pub admins: Vec<Addr>, // the given addresses that are admins, sorted and deduplicated
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct DelegationsResp {
+    pub delegations: Vec<(Addr, Addr, Timestamp)>, // (admin, delegate, until), expired ones included
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct EffectiveAuthorityResp {
+    pub authority: Option<Addr>, // the sender itself if it is an active admin, otherwise the admin it is a delegate of
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct PayoutPlanResp {
+    pub payouts: Vec<(Addr, Vec<Coin>)>, // one entry per active admin, empty if nothing reaches them
+    pub leftover: Vec<Coin>,             // what would stay in the contract
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct FreezeStatusResp {
+    pub frozen: bool,
+    pub freeze_at: Option<Timestamp>, // a proposed freeze, in effect once the block time reaches it
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct StatusCountsResp {
+    pub active: u32,
+    pub expired: u32,
+    pub pending: u32, // proposed through ProposeAdmin but not accepted yet, as listed by PendingSelfAccept
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct IsBrickedResp {
+    pub bricked: bool, // no admins left, nobody can add admins anymore
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct AdminsAddedBetweenResp {
+    pub admins: Vec<Addr>, // in the canonical admin ordering
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct PolicyResp {
+    pub owner: Addr,
+    pub require_acceptance: bool, // AddMembers and MergeFrom are disabled, new admins have to self-accept
+    pub owner_must_be_admin: bool,
+    pub default_role: Role,
+    pub role_capabilities: Vec<(Role, Capabilities)>, // effective capabilities of every role, defaults included
+    pub removal_threshold: Option<u32>,
+    pub max_total_weight: Option<u64>,
+    pub max_admins: Option<u32>,
+    pub per_admin_cooldown: Option<u64>,
+    pub min_tenure: Option<u64>,
+    pub transfer_delay: u64,
+    pub freeze_delay: u64,
+    pub proposal_ttl: Option<u64>,
+    pub rate_guard: Option<RateGuard>,
+    pub frozen: bool,
+    pub read_only: bool,
+    pub paused_until: Option<Timestamp>, // maintenance_until, unless that is already over
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct TotalDonatedResp {
+    pub total: Vec<Coin>, // everything ever donated, including at instantiation, ordered by denom
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct CountResp {
+    pub count: u32,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct AdminsByRankResp {
+    pub admins: Vec<(Addr, u32)>, // (admin, rank) by rank ascending, ties broken by address bytes
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct PendingRemovalsResp {
+    pub removals: Vec<(Addr, Vec<Addr>)>, // (admin proposed for removal, approvers in approval order)
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct PrunableProposalsResp {
+    pub additions: Vec<Addr>, // expired ProposeAdmin proposals, by proposed address
+    pub removals: Vec<Addr>,  // expired ProposeRemove proposals, by admin proposed for removal
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct InactiveSinceResp {
+    pub admins: Vec<Addr>, // in the canonical admin ordering
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct AdminsByMinWeightResp {
+    pub admins: Vec<(Addr, u64)>, // (admin, weight) for every admin with at least the requested weight
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub enum LogExecuteMsg { // message sent to the configured log contract for audit
+    Log {
+        action: String,
+        sender: Addr,
+        affected: Vec<Addr>,
+    },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct Cw4Member {
+    pub addr: String,
+    pub weight: u64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub enum NotifierExecuteMsg { // welcome message sent to the configured notifier when an admin joins
+    Welcome { admin: Addr },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub enum AllowlistQueryMsg { // query sent to the configured allowlist contract for every new admin
+    IsAllowed { addr: String },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct IsAllowedResp {
+    pub allowed: bool,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw4ExecuteMsg { // the subset of the cw4-group execute interface we send to the mirrored group
+    UpdateMembers {
+        remove: Vec<String>,
+        add: Vec<Cw4Member>,
+    },
+}
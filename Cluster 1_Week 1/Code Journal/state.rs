@@ -1,5 +1,288 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use crate::error::ContractError;
+use cosmwasm_std::{
+    Addr, Api, BlockInfo, Coin, Env, Order, StdResult, Storage, Timestamp, Uint128,
+};
+use cw_storage_plus::{Bound, Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-pub const ADMINS: Item<Vec<Addr>> = Item::new("admins"); // defining our admin address for the contract
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, JsonSchema)]
+pub enum Role {
+    Super,
+    Moderator,
+}
 
+impl Role {
+    // storage key in ROLE_CAPABILITIES
+    pub fn key(&self) -> &'static str {
+        match self {
+            Role::Super => "super",
+            Role::Moderator => "moderator",
+        }
+    }
+
+    // what a role can do until SetRoleCapabilities changes it, the same as before roles were configurable
+    pub fn default_capabilities(&self) -> Capabilities {
+        Capabilities {
+            can_add: true,
+            can_remove: *self == Role::Super,
+            can_pause: false,
+            can_config: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+pub struct Capabilities {
+    pub can_add: bool,    // AddMembers, ProposeAdmin, and CreateInvite for supers
+    pub can_remove: bool, // RemoveMembers, ProposeRemove and ApproveRemove
+    pub can_pause: bool,  // SetMaintenance, otherwise owner only
+    pub can_config: bool, // UpdateConfig, otherwise owner only
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct AdminInfo {
+    pub joined: Timestamp, // block time the admin was added at
+    pub joined_height: u64, // block height the admin was added at
+    pub role: Role,
+    pub weight: u64,
+    pub expires_at: u64, // block height the admin expires at, 0 means never
+    // Sender that added the admin: the owner for admins set at instantiate, the invite's creator
+    // for RedeemInvite, the proposer for AcceptAdmin and the previous owner for an owner joining
+    // through AcceptOwnership. The fallback admin and admins added by migrate or Recover are
+    // recorded with the contract address, ImportRoster keeps the exported value.
+    pub added_by: Addr,
+    pub rank: u32, // display position for AdminsByRank, lower comes first
+    // Bumped by every change to the admin's metadata. SetLabel, SetMyLabel, SetAnnotation,
+    // ClearAnnotations and SetRank go through bump_version and check an expected version.
+    // SetLabels and Reorder (bulk setup, no expected version), SwapRoles and ExtendExpiry bump it
+    // without one.
+    pub version: u64,
+}
+
+impl AdminInfo {
+    // plain admins get full rights, a weight of 1, rank 0, version 0 and never expire
+    pub fn new(joined: &BlockInfo, added_by: Addr) -> Self {
+        AdminInfo {
+            joined: joined.time,
+            joined_height: joined.height,
+            role: Role::Super,
+            weight: 1,
+            expires_at: 0,
+            added_by,
+            rank: 0,
+            version: 0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Invite {
+    pub created_by: Addr, // recorded as added_by of whoever redeems the invite
+    pub role: Role,
+    pub weight: u64,
+    pub expires_at: Option<u64>, // block height after which the invite can no longer be redeemed
+}
+
+// Every expiry check goes through here: an expiry height of 0 means "never expires", otherwise
+// the expiry height itself is already expired. Only compares heights, never subtracts, so it
+// can't wrap.
+pub fn is_expired(expires_at: u64, env: &Env) -> bool {
+    expires_at != 0 && env.block.height >= expires_at
+}
+
+pub fn is_active(admin_info: &AdminInfo, env: &Env) -> bool {
+    !is_expired(admin_info.expires_at, env)
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct Config {
+    pub log_contract: Option<Addr>,
+    pub greeting_locked: bool,
+    pub strict_limits: bool,
+    pub cw4_group: Option<Addr>,
+    pub min_delegation: Option<Uint128>,
+    pub default_role: Role,
+    pub transfer_delay: u64,
+    pub min_tenure: Option<u64>,
+    pub maintenance_until: Option<Timestamp>, // mutating execs are rejected until this block time
+    pub migration_source: Option<Addr>, // the only contract allowed to send ImportRoster
+    pub per_admin_cooldown: Option<u64>, // seconds an admin has to wait between two execs
+    pub allowlist_contract: Option<Addr>, // contract every admin added by an exec is checked against, recover, the fallback admin and the owner joining aren't
+    pub require_acceptance: bool, // disables AddMembers and MergeFrom, new admins have to go through ProposeAdmin/AcceptAdmin. Invites still work since the invitee redeems them, and so does ImportRoster which only restores earlier admins
+    pub fallback_admin: Option<Addr>, // added automatically whenever the roster would otherwise be empty
+    pub max_label_len: u32, // enforced by validate_label wherever a label is set
+    pub notify_on_join: Option<Addr>, // notifier contract sent a welcome message for every new admin
+    pub removal_threshold: Option<u32>, // approvals needed to remove an admin, RemoveMembers is disabled when set
+    pub owner_must_be_admin: bool, // the owner can't be removed from the admins and a new owner is added on accept
+    pub blocked_denoms: Vec<String>, // denoms DonateTo refuses even with a min_donations entry, there is no denom allowlist to rank it against
+    pub rate_guard: Option<RateGuard>, // set through SetRateGuard
+    pub max_total_weight: Option<u64>, // cap on the summed weight of all admins
+    pub freeze_delay: u64, // seconds between ProposeFreeze and the freeze taking effect
+    pub proposal_ttl: Option<u64>, // seconds a pending add or remove proposal stays valid
+    pub max_admins: Option<u32>, // cap on the number of admins
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+pub struct RateGuard {
+    pub max_changes: u64, // roster changes allowed within `window`, one more pauses the contract
+    pub window: u64,      // seconds
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct PendingOwner {
+    pub addr: Addr,
+    pub proposed_at: Timestamp,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const CREATED: Item<(u64, Timestamp)> = Item::new("created"); // block height and time of instantiation
+pub const GREETING: Item<String> = Item::new("greeting");
+pub const OWNER: Item<Addr> = Item::new("owner"); // the address that instantiated the contract
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+pub const FROZEN: Item<bool> = Item::new("frozen"); // set once the roster is exported, rejects every exec afterwards
+pub const FREEZE_AT: Item<Timestamp> = Item::new("freeze_at"); // set by ProposeFreeze, the contract counts as frozen from then on
+pub const READ_ONLY: Item<bool> = Item::new("read_only"); // only set and lifted through sudo, rejects every exec while true
+pub const ADMINS: Map<&Addr, AdminInfo> = Map::new("admin_info"); // defining our admin addresses for the contract, keyed by address
+pub const ADMINS_BY_JOINED: Map<(u64, &[u8]), Addr> = Map::new("admins_by_joined"); // (join time in nanos, canonical address) -> admin, the index behind admins_ordered
+pub const LEGACY_ADMINS: Item<Vec<Addr>> = Item::new("admins"); // the original layout, a plain list of admins, only read by migrate
+pub const STATE_VERSION: Item<u32> = Item::new("state_version"); // layout version of the stored state, unset on deployments older than version 1
+pub const ADMIN_COUNT: Item<u32> = Item::new("admin_count"); // number of entries in ADMINS, kept in sync by add_admin/remove_admin_fully
+pub const ROSTER_CHANGES: Item<u64> = Item::new("roster_changes"); // admins ever added or removed, bumped by add_admin/remove_admin_fully
+pub const RECENT_CHANGES: Item<Vec<(Timestamp, u64)>> = Item::new("recent_changes"); // (block time, roster changes) of execs inside the rate guard window
+pub const PROPOSED_ADMINS: Map<&Addr, Addr> = Map::new("proposed_admins"); // proposed address -> admin who proposed it
+pub const PENDING_REMOVALS: Map<&Addr, Vec<Addr>> = Map::new("pending_removals"); // admin proposed for removal -> admins who approved so far
+pub const PROPOSED_AT: Map<&Addr, Timestamp> = Map::new("proposed_at"); // block time of each PROPOSED_ADMINS entry, for proposal_ttl
+pub const REMOVAL_PROPOSED_AT: Map<&Addr, Timestamp> = Map::new("removal_proposed_at"); // block time of each PENDING_REMOVALS entry, for proposal_ttl
+pub const ROLE_CAPABILITIES: Map<&str, Capabilities> = Map::new("role_capabilities"); // keyed by Role::key, unset roles use their defaults
+pub const MIN_DONATIONS: Map<&str, Uint128> = Map::new("min_donations"); // smallest amount DonateTo accepts per denom
+pub const TOTAL_DONATED: Map<&str, Uint128> = Map::new("total_donated"); // cumulative donations per denom, never reduced by payouts
+pub const DELEGATIONS: Map<&Addr, (Addr, Timestamp)> = Map::new("delegations"); // admin -> (delegate, block time the delegation ends at)
+pub const INVITES: Map<&[u8], Invite> = Map::new("invites"); // pending invites keyed by sha256 of the invite code
+pub const LAST_ACTION: Map<&Addr, Timestamp> = Map::new("last_action"); // block time of each admin's last exec, for per_admin_cooldown and inactivity
+pub const LABELS: Map<&Addr, String> = Map::new("labels"); // display label per admin
+pub const ANNOTATIONS: Map<(&Addr, &str), String> = Map::new("annotations"); // free-form key/value notes per admin
+
+pub fn role_capabilities(storage: &dyn Storage, role: Role) -> StdResult<Capabilities> {
+    Ok(ROLE_CAPABILITIES
+        .may_load(storage, role.key())?
+        .unwrap_or_else(|| role.default_capabilities()))
+}
+
+// adds `funds` to the cumulative TOTAL_DONATED
+pub fn record_donation(storage: &mut dyn Storage, funds: &[Coin]) -> StdResult<()> {
+    for fund in funds {
+        TOTAL_DONATED.update(storage, &fund.denom, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().checked_add(fund.amount)?)
+        })?;
+    }
+    Ok(())
+}
+
+fn bump_roster_changes(storage: &mut dyn Storage) -> StdResult<()> {
+    let changes = ROSTER_CHANGES.may_load(storage)?.unwrap_or_default();
+    ROSTER_CHANGES.save(storage, &(changes + 1))
+}
+
+// saves a new admin and bumps the counter, returns false if the address already was an admin
+pub fn add_admin(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    addr: &Addr,
+    info: &AdminInfo,
+) -> StdResult<bool> {
+    if ADMINS.has(storage, addr) {
+        return Ok(false);
+    }
+
+    let canonical = api.addr_canonicalize(addr.as_str())?;
+    ADMINS.save(storage, addr, info)?;
+    ADMINS_BY_JOINED.save(storage, (info.joined.nanos(), canonical.as_slice()), addr)?;
+    let count = ADMIN_COUNT.may_load(storage)?.unwrap_or_default();
+    ADMIN_COUNT.save(storage, &(count + 1))?;
+    bump_roster_changes(storage)?;
+    Ok(true)
+}
+
+// Removes an admin along with everything stored about them (role, weight and join time live in
+// ADMINS and its ADMINS_BY_JOINED entry, plus their label, annotations, last action, pending removal and delegation) and
+// decrements the counter. Every removal path goes through here so nothing is left orphaned.
+// Returns false if the address was not an admin. A counter already at zero means it lost sync with ADMINS, that fails with CountUnderflow before
+// anything is removed instead of wrapping around.
+pub fn remove_admin_fully(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    addr: &Addr,
+) -> Result<bool, ContractError> {
+    let admin_info = match ADMINS.may_load(storage, addr)? {
+        Some(admin_info) => admin_info,
+        None => return Ok(false),
+    };
+    let count = ADMIN_COUNT
+        .load(storage)?
+        .checked_sub(1)
+        .ok_or(ContractError::CountUnderflow {})?;
+    let canonical = api.addr_canonicalize(addr.as_str())?;
+
+    ADMINS.remove(storage, addr);
+    ADMINS_BY_JOINED.remove(storage, (admin_info.joined.nanos(), canonical.as_slice()));
+    let keys = ANNOTATIONS
+        .prefix(addr)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for key in keys {
+        ANNOTATIONS.remove(storage, (addr, key.as_str()));
+    }
+    LABELS.remove(storage, addr);
+    LAST_ACTION.remove(storage, addr);
+    PENDING_REMOVALS.remove(storage, addr);
+    REMOVAL_PROPOSED_AT.remove(storage, addr);
+    DELEGATIONS.remove(storage, addr);
+    ADMIN_COUNT.save(storage, &count)?;
+    bump_roster_changes(storage)?;
+    Ok(true)
+}
+
+// Canonical admin ordering used by every ordered query: join time ascending, and admins who joined
+// at the same time are ordered by the bytes of their canonical address, so all nodes return exactly the same order.
+// That is the key order of ADMINS_BY_JOINED, so a page only reads the admins on it.
+// `start_after` has to be an admin, its join time locates it in the index.
+pub fn admins_ordered_page(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    start_after: Option<&Addr>,
+    limit: usize,
+) -> StdResult<Vec<(Addr, AdminInfo)>> {
+    let start = match start_after {
+        Some(addr) => {
+            let joined = ADMINS.load(storage, addr)?.joined;
+            Some((joined.nanos(), api.addr_canonicalize(addr.as_str())?))
+        }
+        None => None,
+    };
+    let start = start
+        .as_ref()
+        .map(|(joined, canonical)| Bound::exclusive((*joined, canonical.as_slice())));
+    index_page(storage, start, limit)
+}
+
+fn index_page<'a>(
+    storage: &dyn Storage,
+    start: Option<Bound<'a, (u64, &'a [u8])>>,
+    limit: usize,
+) -> StdResult<Vec<(Addr, AdminInfo)>> {
+    ADMINS_BY_JOINED
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|entry| {
+            let (_, addr) = entry?;
+            let admin_info = ADMINS.load(storage, &addr)?;
+            Ok((addr, admin_info))
+        })
+        .collect()
+}
+
+// the whole roster in the canonical ordering, for the queries and execs that go through all of it
+pub fn admins_ordered(storage: &dyn Storage) -> StdResult<Vec<(Addr, AdminInfo)>> {
+    index_page(storage, None, usize::MAX)
+}